@@ -0,0 +1,88 @@
+//! Compara el análisis de un proyecto sintético con un solo hilo y con
+//! todos los núcleos disponibles.
+//!
+//! cargo bench --bench project_analysis -- [archivos] [hilos]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tree_sitter_test::{Analyzer, ProjectOptions};
+
+const PYTHON_MODULE: &str = "\
+from pkg.shared import helper
+import os
+
+class Service{n}:
+    def __init__(self, name: str):
+        self.name = name
+
+    def run(self, items: list, retries: int = 3) -> int:
+        total = 0
+        for item in items:
+            total += helper(item)
+        return total
+
+def build_{n}(path: str) -> Service{n}:
+    base = os.path.basename(path)
+    return Service{n}(base)
+";
+
+const TS_MODULE: &str = "\
+import { helper } from './shared';
+
+export class Service{n} {
+    constructor(private name: string) {}
+
+    run(items: number[], retries: number = 3): number {
+        let total = 0;
+        for (const item of items) {
+            total += helper(item);
+        }
+        return total;
+    }
+}
+
+export const build{n} = (path: string): Service{n} => new Service{n}(path);
+";
+
+fn generate_fixture(root: &Path, files: usize) {
+    let _ = fs::remove_dir_all(root);
+    fs::create_dir_all(root.join("pkg")).unwrap();
+    fs::create_dir_all(root.join("web")).unwrap();
+    fs::write(root.join("pkg/shared.py"), "def helper(x):\n    return x\n").unwrap();
+    fs::write(root.join("web/shared.ts"), "export function helper(x: number): number { return x; }\n").unwrap();
+
+    for n in 0..files / 2 {
+        fs::write(root.join(format!("pkg/module_{}.py", n)), PYTHON_MODULE.replace("{n}", &n.to_string())).unwrap();
+        fs::write(root.join(format!("web/module_{}.ts", n)), TS_MODULE.replace("{n}", &n.to_string())).unwrap();
+    }
+}
+
+fn measure(root: &Path, workers: usize) -> (Duration, usize) {
+    let analyzer = Analyzer::new(vec![root.to_path_buf()]);
+    let options = ProjectOptions::new().workers(workers);
+
+    let start = Instant::now();
+    let project = analyzer.analyze_project(root, &options).unwrap();
+    (start.elapsed(), project.files.len())
+}
+
+fn main() {
+    let numbers: Vec<usize> = std::env::args().skip(1).filter_map(|a| a.parse().ok()).collect();
+    let files = numbers.first().copied().unwrap_or(2000);
+    let workers = numbers.get(1).copied().unwrap_or_else(|| ProjectOptions::new().worker_count());
+    let root: PathBuf = std::env::temp_dir().join("tree_sitter_test_bench_fixture");
+    generate_fixture(&root, files);
+
+    // Primera pasada para calentar la caché del sistema de archivos
+    measure(&root, workers);
+
+    let (single, analyzed) = measure(&root, 1);
+    let (parallel, _) = measure(&root, workers);
+
+    println!("archivos analizados: {}", analyzed);
+    println!("{:<12} {:>8.1} ms", "1 hilo:", single.as_secs_f64() * 1000.0);
+    println!("{:<12} {:>8.1} ms", format!("{} hilos:", workers), parallel.as_secs_f64() * 1000.0);
+    println!("aceleración: {:.2}x", single.as_secs_f64() / parallel.as_secs_f64());
+}
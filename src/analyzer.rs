@@ -0,0 +1,68 @@
+use std::{fs, io, path::Path, path::PathBuf};
+
+use crate::error::AnalysisError;
+use crate::graph;
+use crate::models::{analysis_result::AnalysisResult, call_graph::CallGraph, project_analysis::ProjectAnalysis};
+use crate::parser::registry::{self, LanguageRegistry};
+use crate::project::{self, ProjectOptions};
+use crate::session::AnalysisSession;
+
+/// Punto de entrada de la biblioteca: analiza archivos con los backends
+/// registrados y devuelve el resultado tipado, sin escribir nada en disco.
+pub struct Analyzer {
+    roots: Vec<PathBuf>,
+    registry: LanguageRegistry,
+}
+
+impl Analyzer {
+    /// Usa los lenguajes del registro global al momento de la llamada.
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        Analyzer { roots, registry: registry::registry() }
+    }
+
+    pub fn with_registry(roots: Vec<PathBuf>, registry: LanguageRegistry) -> Self {
+        Analyzer { roots, registry }
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    pub fn registry(&self) -> &LanguageRegistry {
+        &self.registry
+    }
+
+    pub fn analyze_source(&self, source: &str, path: &Path) -> Result<AnalysisResult, AnalysisError> {
+        let analyzer = self.registry.for_path(path)
+            .ok_or_else(|| AnalysisError::UnsupportedLanguage(path.to_path_buf()))?;
+        analyzer.analyze(source, path, &self.roots)
+    }
+
+    pub fn analyze_file(&self, path: &Path) -> Result<AnalysisResult, AnalysisError> {
+        let source = read_source(path)?;
+        self.analyze_source(&source, path)
+    }
+
+    /// Analiza todos los archivos soportados bajo `root`. Los archivos con
+    /// extensión no soportada se ignoran y los que fallan quedan en `errors`.
+    pub fn analyze_project(&self, root: &Path, options: &ProjectOptions) -> Result<ProjectAnalysis, AnalysisError> {
+        project::analyze_project(self, root, options)
+    }
+
+    pub fn call_graph(&self, project: &ProjectAnalysis) -> CallGraph {
+        graph::build_call_graph(project, &self.registry)
+    }
+
+    /// Sesión de análisis incremental para un archivo abierto en un editor.
+    pub fn session(&self, path: &Path, source: impl Into<String>) -> Result<AnalysisSession, AnalysisError> {
+        AnalysisSession::new(self, path, source)
+    }
+}
+
+
+pub(crate) fn read_source(path: &Path) -> Result<String, AnalysisError> {
+    fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::InvalidData => AnalysisError::InvalidUtf8(path.to_path_buf()),
+        _ => AnalysisError::Io { path: path.to_path_buf(), source: e },
+    })
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::AnalysisError;
+use crate::models::analysis_result::AnalysisResult;
+use crate::parser::probe::Probe;
+
+/// Caché en disco de `AnalysisResult`s serializados. Cada entrada se indexa
+/// por el contenido del archivo, su ruta, el lenguaje, la versión del crate
+/// y las raíces de resolución, y guarda las rutas que consultó la resolución
+/// de sus imports (ver `parser::probe`). Nunca se devuelve un resultado
+/// viejo: si alguna de esas rutas cambió (apareció el módulo que faltaba,
+/// se editó un `tsconfig.json` o un `package.json`), la entrada se descarta;
+/// cambios en el resto del proyecto no la afectan.
+#[derive(Debug, Clone)]
+pub struct AnalysisCache {
+    dir: PathBuf,
+    hits: Arc<AtomicUsize>,
+    misses: Arc<AtomicUsize>,
+}
+
+impl AnalysisCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        AnalysisCache { dir: dir.into(), hits: Arc::default(), misses: Arc::default() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn key(&self, source: &str, path: &Path, language: &str, roots: &[PathBuf]) -> String {
+        let mut hasher = Sha256::new();
+        for part in [env!("CARGO_PKG_VERSION"), language, &path.to_string_lossy()] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        for root in roots {
+            hasher.update(root.to_string_lossy().as_bytes());
+            hasher.update([0]);
+        }
+        hasher.update(Sha256::digest(source.as_bytes()));
+        to_hex(&hasher.finalize())
+    }
+
+    /// Resultado guardado con `key`, si todas las rutas de las que dependía
+    /// siguen como estaban.
+    pub fn get(&self, key: &str) -> Option<AnalysisResult> {
+        let result = fs::read_to_string(self.entry_path(key))
+            .ok()
+            .and_then(|json| serde_json::from_str::<Entry>(&json).ok())
+            .filter(|entry| entry.dependencies.iter().all(Probe::is_current))
+            .map(|entry| entry.result);
+
+        match result {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        result
+    }
+
+    /// Guarda `result` junto con las consultas al sistema de archivos de las
+    /// que depende (las que devuelve `probe::record` al analizar).
+    pub fn put(&self, key: &str, result: &AnalysisResult, dependencies: &[Probe]) -> Result<(), AnalysisError> {
+        let path = self.entry_path(key);
+        let entry = EntryRef { dependencies, result };
+        let json = serde_json::to_string(&entry).map_err(|e| AnalysisError::Output(e.to_string()))?;
+
+        let parent = path.parent().unwrap_or(&self.dir);
+        fs::create_dir_all(parent)
+            .map_err(|e| AnalysisError::Output(format!("could not create {}: {}", parent.display(), e)))?;
+
+        // Escritura atómica: otro hilo puede estar leyendo la misma entrada
+        let writer = format!("{}-{:?}", std::process::id(), std::thread::current().id()).replace(['(', ')'], "");
+        let tmp = path.with_extension(format!("{}.tmp", writer));
+        fs::write(&tmp, json)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| AnalysisError::Output(format!("could not write {}: {}", path.display(), e)))
+    }
+
+    pub fn clear(&self) -> Result<(), AnalysisError> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(AnalysisError::Output(format!("could not remove {}: {}", self.dir.display(), e)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// El nombre del archivo es el hash de la clave, así que cualquier clave
+    /// es válida (vacía, corta o con `/`); las entradas se reparten en
+    /// subdirectorios por sus dos primeros caracteres.
+    fn entry_path(&self, key: &str) -> PathBuf {
+        let name = to_hex(&Sha256::digest(key.as_bytes()));
+        self.dir.join(&name[..2]).join(format!("{}.json", name))
+    }
+}
+
+
+#[derive(Deserialize)]
+struct Entry {
+    dependencies: Vec<Probe>,
+    result: AnalysisResult,
+}
+
+#[derive(Serialize)]
+struct EntryRef<'a> {
+    dependencies: &'a [Probe],
+    result: &'a AnalysisResult,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,35 @@
+use std::{fmt, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum AnalysisError {
+    UnsupportedLanguage(PathBuf),
+    Io { path: PathBuf, source: io::Error },
+    InvalidUtf8(PathBuf),
+    Parse { path: PathBuf, message: String },
+    Output(String),
+    InvalidPattern { pattern: String, message: String },
+    InvalidEdit(String),
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalysisError::UnsupportedLanguage(path) => write!(f, "Unsupported file type: {}", path.display()),
+            AnalysisError::Io { path, source } => write!(f, "Could not read {}: {}", path.display(), source),
+            AnalysisError::InvalidUtf8(path) => write!(f, "Invalid UTF-8 in {}", path.display()),
+            AnalysisError::Parse { path, message } => write!(f, "Failed to parse {}: {}", path.display(), message),
+            AnalysisError::Output(message) => write!(f, "Failed to write output: {}", message),
+            AnalysisError::InvalidPattern { pattern, message } => write!(f, "Invalid pattern {}: {}", pattern, message),
+            AnalysisError::InvalidEdit(message) => write!(f, "Invalid edit: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnalysisError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::analysis_result::AnalysisResult;
+use crate::models::call_graph::{CallEdge, CallGraph, CallTarget, SymbolRef};
+use crate::models::class_info::ClassInfo;
+use crate::models::function_call::FunctionCall;
+use crate::models::function_info::FunctionInfo;
+use crate::models::import_info::{ImportInfo, ImportKind};
+use crate::models::project_analysis::ProjectAnalysis;
+use crate::parser::registry::LanguageRegistry;
+
+/// Construye el grafo de llamadas del proyecto, vinculando cada
+/// `FunctionCall` con su definición a través de `ImportInfo.path`.
+pub fn build_call_graph(project: &ProjectAnalysis, registry: &LanguageRegistry) -> CallGraph {
+    let index = FileIndex::new(project);
+    let mut graph = CallGraph::default();
+
+    for (file, result) in &project.files {
+        let resolver = Resolver { project, index: &index, registry, file, result };
+        resolver.visit(&mut graph, &result.functions, &result.classes, None, &mut vec![]);
+    }
+
+    graph
+}
+
+
+/// Traduce los paths absolutos de `ImportInfo.path` a las claves relativas
+/// de `ProjectAnalysis.files`.
+struct FileIndex {
+    by_absolute: HashMap<PathBuf, PathBuf>,
+}
+
+impl FileIndex {
+    fn new(project: &ProjectAnalysis) -> Self {
+        let by_absolute = project.files.keys()
+            .filter_map(|relative| {
+                let absolute = project.root.join(relative).canonicalize().ok()?;
+                Some((absolute, relative.clone()))
+            })
+            .collect();
+        FileIndex { by_absolute }
+    }
+
+    fn lookup(&self, path: &Path) -> Option<&PathBuf> {
+        self.by_absolute.get(path)
+    }
+
+    /// Archivo del proyecto para un import resuelto. Un paquete de Python
+    /// puede haberse resuelto al directorio o a su `__init__.py`.
+    fn module_file(&self, path: &Path) -> Option<&PathBuf> {
+        if let Some(found) = self.lookup(path) {
+            return Some(found);
+        }
+        package_dir(path)
+            .and_then(|dir| dir.join("__init__.py").canonicalize().ok())
+            .and_then(|init| self.lookup(&init))
+    }
+
+    /// Submódulo `member` de un paquete de Python (`from pkg import mod`).
+    fn submodule(&self, path: &Path, member: &str) -> Option<&PathBuf> {
+        let dir = package_dir(path)?;
+        [dir.join(format!("{}.py", member)), dir.join(member).join("__init__.py")]
+            .into_iter()
+            .find_map(|candidate| candidate.canonicalize().ok().and_then(|c| self.lookup(&c)))
+    }
+}
+
+/// Directorio del paquete si el import se resolvió al paquete o a su
+/// `__init__.py`.
+fn package_dir(path: &Path) -> Option<PathBuf> {
+    if path.is_dir() {
+        Some(path.to_path_buf())
+    } else if path.file_name().is_some_and(|n| n == "__init__.py") {
+        path.parent().map(|p| p.to_path_buf())
+    } else {
+        None
+    }
+}
+
+
+struct Resolver<'a> {
+    project: &'a ProjectAnalysis,
+    index: &'a FileIndex,
+    registry: &'a LanguageRegistry,
+    file: &'a PathBuf,
+    result: &'a AnalysisResult,
+}
+
+impl<'a> Resolver<'a> {
+    /// Recorre funciones y clases, incluidas las anidadas. `class` es la
+    /// clase de la que `functions` son métodos; `enclosing`, las funciones
+    /// que rodean a `functions`, de la más externa a la más interna (el
+    /// cuerpo de una clase no abre un scope para sus métodos).
+    fn visit(
+        &self,
+        graph: &mut CallGraph,
+        functions: &'a [FunctionInfo],
+        classes: &'a [ClassInfo],
+        class: Option<&'a ClassInfo>,
+        enclosing: &mut Vec<&'a FunctionInfo>,
+    ) {
+        for function in functions {
+            enclosing.push(function);
+            self.add_edges(graph, class, enclosing);
+            self.visit(graph, &function.functions, &function.classes, None, enclosing);
+            enclosing.pop();
+        }
+        for nested in classes {
+            self.visit(graph, &nested.methods, &nested.classes, Some(nested), enclosing);
+        }
+    }
+
+    /// Aristas de la última función de `scopes`, que puede llamar a las
+    /// funciones y clases locales de cualquiera de ellas.
+    fn add_edges(&self, graph: &mut CallGraph, class: Option<&ClassInfo>, scopes: &[&FunctionInfo]) {
+        let Some(function) = scopes.last() else { return };
+        let caller = function_ref(self.file, class, function);
+
+        for call in function.function_calls.iter().flatten() {
+            graph.edges.push(CallEdge {
+                caller: caller.clone(),
+                callee: self.resolve(call, class, scopes),
+                name: call.name.clone(),
+                line: call.line,
+            });
+        }
+    }
+
+    fn resolve(&self, call: &FunctionCall, class: Option<&ClassInfo>, scopes: &[&FunctionInfo]) -> CallTarget {
+        if let Some(import_name) = &call.import_name {
+            return self.resolve_imported(import_name, &call.name);
+        }
+
+        if let Some(object) = &call.object_name {
+            // Llamada a un método de la propia clase
+            if object == "self" || object == "this" {
+                return class
+                    .and_then(|c| method_ref(self.file, c, &call.name))
+                    .unwrap_or(CallTarget::Unknown);
+            }
+
+            // Variable local instanciada a partir de una clase conocida
+            let assigned_class = scopes.last()
+                .and_then(|f| f.local_variables.iter().find(|v| &v.name == object))
+                .and_then(|v| v.assigned_from.as_deref());
+            if let Some(class_name) = assigned_class
+                && let Some((file, class)) = self.find_class(class_name, scopes)
+                && let Some(target) = method_ref(file, class, &call.name)
+            {
+                return target;
+            }
+
+            if self.is_builtin(object) {
+                return CallTarget::Builtin;
+            }
+            return CallTarget::Unknown;
+        }
+
+        if let Some(target) = local_symbol(self.file, scopes, &call.name) {
+            return target;
+        }
+        if let Some(target) = symbol_in(self.file, self.result, &call.name) {
+            return target;
+        }
+        if self.is_builtin(&call.name) {
+            return CallTarget::Builtin;
+        }
+        CallTarget::Unknown
+    }
+
+    /// Llamada a través de un import. `import_name` es el módulo, o el nombre
+    /// importado cuando se usa como namespace (`import { utils }` →
+    /// `utils.f()`, `from pkg import mod` → `mod.f()`). Si varios imports
+    /// encajan y no llevan al mismo destino, la llamada es ambigua.
+    fn resolve_imported(&self, import_name: &str, name: &str) -> CallTarget {
+        let modules = self.result.imports.iter()
+            .filter(|i| i.name == import_name)
+            .map(|i| self.resolve_in_module(i, name));
+        let members = self.result.imports.iter()
+            .flat_map(|i| i.imported_names.iter().map(move |n| (i, n)))
+            .filter(|(_, n)| n.kind == ImportKind::Named && n.name == import_name)
+            .map(|(i, n)| self.resolve_in_member(i, &n.name, name));
+
+        let mut targets = modules.chain(members);
+        let Some(first) = targets.next() else { return CallTarget::Unknown };
+        if targets.all(|t| t == first) { first } else { CallTarget::Unknown }
+    }
+
+    /// `name` definido en el nivel superior del módulo importado.
+    fn resolve_in_module(&self, import: &ImportInfo, name: &str) -> CallTarget {
+        let Some(target_file) = import.path.as_deref().and_then(|p| self.index.module_file(p)) else {
+            return unresolved(import);
+        };
+        self.project.files.get(target_file)
+            .and_then(|target| symbol_in(target_file, target, name))
+            .unwrap_or(CallTarget::Unknown)
+    }
+
+    /// `name` dentro del nombre `member` importado del módulo: un submódulo
+    /// del paquete o una clase exportada.
+    fn resolve_in_member(&self, import: &ImportInfo, member: &str, name: &str) -> CallTarget {
+        let Some(path) = &import.path else { return unresolved(import) };
+        if let Some(target_file) = self.index.submodule(path, member) {
+            return self.project.files.get(target_file)
+                .and_then(|target| symbol_in(target_file, target, name))
+                .unwrap_or(CallTarget::Unknown);
+        }
+        let Some(target_file) = self.index.module_file(path) else { return unresolved(import) };
+
+        self.project.files.get(target_file)
+            .and_then(|target| target.classes.iter().find(|c| c.name == member))
+            .and_then(|class| member_of(target_file, class, name))
+            .unwrap_or(CallTarget::Unknown)
+    }
+
+    /// Clase local de alguna de las funciones que rodean la llamada, definida
+    /// en este archivo o importada por nombre desde otro.
+    fn find_class(&self, name: &str, scopes: &[&'a FunctionInfo]) -> Option<(&'a PathBuf, &'a ClassInfo)> {
+        if let Some(class) = scopes.iter().rev().find_map(|f| f.classes.iter().find(|c| c.name == name)) {
+            return Some((self.file, class));
+        }
+        if let Some(class) = self.result.classes.iter().find(|c| c.name == name) {
+            return Some((self.file, class));
+        }
+
+        let (import, imported) = self.result.imports.iter()
+            .find_map(|i| i.imported(name).map(|n| (i, n)))?;
+        let target_file = self.index.module_file(import.path.as_ref()?)?;
+        let class = self.project.files.get(target_file)?.classes.iter().find(|c| c.name == imported.name)?;
+        Some((target_file, class))
+    }
+
+    fn is_builtin(&self, name: &str) -> bool {
+        self.registry.for_path(self.file).is_some_and(|a| a.is_builtin(name))
+    }
+}
+
+
+/// Función o clase local de alguna de las funciones que rodean la llamada,
+/// de la más interna a la más externa.
+fn local_symbol(file: &Path, scopes: &[&FunctionInfo], name: &str) -> Option<CallTarget> {
+    scopes.iter().rev().find_map(|scope| {
+        let function = scope.functions.iter().find(|f| f.name == name).map(|f| function_ref(file, None, f));
+        function.or_else(|| scope.classes.iter().find(|c| c.name == name).map(|c| class_ref(file, c)))
+    }).map(CallTarget::Resolved)
+}
+
+fn symbol_in(file: &Path, result: &AnalysisResult, name: &str) -> Option<CallTarget> {
+    if let Some(function) = result.functions.iter().find(|f| f.name == name) {
+        return Some(CallTarget::Resolved(function_ref(file, None, function)));
+    }
+    result.classes.iter().find(|c| c.name == name).map(|class| CallTarget::Resolved(class_ref(file, class)))
+}
+
+/// Método o clase anidada de `class`.
+fn member_of(file: &Path, class: &ClassInfo, name: &str) -> Option<CallTarget> {
+    method_ref(file, class, name)
+        .or_else(|| class.classes.iter().find(|c| c.name == name).map(|nested| CallTarget::Resolved(class_ref(file, nested))))
+}
+
+/// Import que no lleva a un archivo del proyecto: un módulo externo si es un
+/// nombre de paquete; desconocido si es una ruta relativa o absoluta
+/// (`./nope`, `from .missing import f`).
+fn unresolved(import: &ImportInfo) -> CallTarget {
+    if import.specifier.starts_with('.') || import.specifier.starts_with('/') {
+        return CallTarget::Unknown;
+    }
+    CallTarget::External { module: import.name.clone() }
+}
+
+fn method_ref(file: &Path, class: &ClassInfo, name: &str) -> Option<CallTarget> {
+    class.methods.iter().find(|m| m.name == name).map(|method| CallTarget::Resolved(function_ref(file, Some(class), method)))
+}
+
+fn function_ref(file: &Path, class: Option<&ClassInfo>, function: &FunctionInfo) -> SymbolRef {
+    SymbolRef {
+        file: file.to_path_buf(),
+        class: class.map(|c| c.name.clone()),
+        function: Some(function.name.clone()),
+        qualified_name: function.qualified_name.clone(),
+        line: function.line,
+    }
+}
+
+fn class_ref(file: &Path, class: &ClassInfo) -> SymbolRef {
+    SymbolRef {
+        file: file.to_path_buf(),
+        class: Some(class.name.clone()),
+        function: None,
+        qualified_name: class.qualified_name.clone(),
+        line: class.line,
+    }
+}
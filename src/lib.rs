@@ -1,25 +1,23 @@
+pub mod analyzer;
+pub mod cache;
+pub mod error;
+pub mod graph;
 pub mod models;
+pub mod output;
 pub mod parser;
+pub mod project;
+pub mod session;
 
-use std::{fs, path::Path, path::PathBuf};
+use std::path::{Path, PathBuf};
 
-pub fn run_analysis(file_path: &Path, root_path: &[PathBuf]) -> Result<String, String> {
-    let cloned_path = file_path;
-    let source_code = match fs::read_to_string(file_path) {
-        Ok(c) => c,
-        Err(e) => return Err(format!("Could not read input file: {}", e)),
-    };
+pub use analyzer::Analyzer;
+pub use cache::AnalysisCache;
+pub use project::ProjectOptions;
+pub use session::{AnalysisSession, TextEdit};
+use error::AnalysisError;
 
-    let result = parser::parse_file(&source_code, cloned_path, root_path);
-    let json = serde_json::to_string_pretty(&result).unwrap();
-
-    let output_dir = PathBuf::from("parsed-files");
-    if let Err(e) = fs::create_dir_all(&output_dir) {
-        return Err(format!("Failed to create output directory: {}", e));
-    }
-    let file_stem = file_path.file_stem().unwrap().to_str().unwrap();
-    let output_path = output_dir.join(format!("{}.json", file_stem));
-    fs::write(&output_path, &json).expect("Failed to write output");
-    
-    Ok(json)
-}
\ No newline at end of file
+/// Analiza un archivo y devuelve el resultado serializado como JSON.
+pub fn run_analysis(file_path: &Path, root_path: &[PathBuf]) -> Result<String, AnalysisError> {
+    let result = Analyzer::new(root_path.to_vec()).analyze_file(file_path)?;
+    output::to_json(&result)
+}
@@ -1,25 +1,155 @@
-mod models;
-mod parser;
-use std::{env, path::{Path, PathBuf}};
-use tree_sitter_test::run_analysis;
+use std::{collections::BTreeMap, env, fs, path::{Path, PathBuf}};
+use tree_sitter_test::{AnalysisCache, Analyzer, ProjectOptions};
+use tree_sitter_test::error::AnalysisError;
+use tree_sitter_test::models::project_analysis::ProjectAnalysis;
+use tree_sitter_test::output::{DirectorySink, OutputSink, StdoutSink};
 
+const INPUT_FILES: &str = "input-files";
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Invalid input parameters");
-        std::process::exit(1);
+const USAGE: &str = "usage: tree-sitter <archivo|directorio> [--stdout] [--out <dir>] [--include <glob>]... [--exclude <glob>]... [--no-gitignore] [--workers <n>] [--cache <dir>] [--call-graph]";
+
+struct Args {
+    target: String,
+    to_stdout: bool,
+    call_graph: bool,
+    out_dir: PathBuf,
+    options: ProjectOptions,
+}
+
+
+fn parse_args() -> Option<Args> {
+    let mut target = None;
+    let mut to_stdout = false;
+    let mut call_graph = false;
+    let mut out_dir = PathBuf::from("parsed-files");
+    let mut options = ProjectOptions::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stdout" => to_stdout = true,
+            "--call-graph" => call_graph = true,
+            "--out" => out_dir = PathBuf::from(args.next()?),
+            "--include" => options = options.include(args.next()?),
+            "--exclude" => options = options.exclude(args.next()?),
+            "--no-gitignore" => options = options.respect_gitignore(false),
+            "--workers" => options = options.workers(args.next()?.parse().ok()?),
+            "--cache" => options = options.cache(AnalysisCache::new(args.next()?)),
+            _ if target.is_none() => target = Some(arg),
+            _ => return None,
+        }
+    }
+
+    Some(Args { target: target?, to_stdout, call_graph, out_dir, options })
+}
+
+
+fn run(args: Args) -> Result<(), AnalysisError> {
+    // Compatibilidad: un nombre suelto se sigue buscando dentro de input-files/
+    let input_files = Path::new(INPUT_FILES);
+    let mut target = PathBuf::from(&args.target);
+    if !target.exists() && input_files.join(&target).exists() {
+        target = input_files.join(&target);
+    }
+
+    // Un archivo dentro de input-files/ se resuelve y se escribe relativo a
+    // input-files/, para que a/utils.py y b/utils.py no se pisen y puedan
+    // importarse entre carpetas
+    let root = if target.is_dir() {
+        target.clone()
+    } else if let Some(relative) = relative_to(&target, input_files) {
+        target = input_files.join(relative);
+        input_files.to_path_buf()
+    } else {
+        target.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new(".")).to_path_buf()
+    };
+    let analyzer = Analyzer::new(vec![root.clone()]);
+
+    if target.is_dir() {
+        let project = analyzer.analyze_project(&target, &args.options)?;
+        for (path, error) in &project.errors {
+            eprintln!("Warning: {}: {}", path.display(), error);
+        }
+
+        if args.call_graph {
+            write_call_graph(&analyzer, &project, &args)?;
+            if args.to_stdout {
+                return Ok(());
+            }
+        }
+
+        if args.to_stdout {
+            let json = serde_json::to_string_pretty(&project).map_err(|e| AnalysisError::Output(e.to_string()))?;
+            println!("{}", json);
+        } else {
+            let mut sink = DirectorySink::new(&args.out_dir);
+            for (path, result) in &project.files {
+                sink.write(path, result)?;
+            }
+            println!("Analysis complete: {} files", project.files.len());
+        }
+        return Ok(());
     }
 
-    let file_name = &args[1];
-    let file_path = format!("input-files/{}", file_name);
+    let result = analyzer.analyze_file(&target)?;
 
-    match run_analysis(Path::new(&file_path), &[PathBuf::from("./input-files")]) {
-        Ok(_) => println!("Analysis complete"),
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+    // Grafo de un proyecto con un solo archivo: llamadas internas, builtins
+    // y a módulos importados
+    if args.call_graph {
+        let relative = target.strip_prefix(&root).map(Path::to_path_buf)
+            .unwrap_or_else(|_| PathBuf::from(target.file_name().unwrap_or_default()));
+        let project = ProjectAnalysis {
+            root: root.clone(),
+            files: BTreeMap::from([(relative, result.clone())]),
+            errors: BTreeMap::new(),
+        };
+        write_call_graph(&analyzer, &project, &args)?;
+        if args.to_stdout {
+            return Ok(());
         }
     }
+
+    if args.to_stdout {
+        StdoutSink.write(&target, &result)?;
+    } else {
+        DirectorySink::new(&args.out_dir).with_base(&root).write(&target, &result)?;
+        println!("Analysis complete");
+    }
+    Ok(())
 }
 
+
+/// Escribe `call-graph.json` en el directorio de salida, o lo imprime con
+/// `--stdout`.
+fn write_call_graph(analyzer: &Analyzer, project: &ProjectAnalysis, args: &Args) -> Result<(), AnalysisError> {
+    let graph = analyzer.call_graph(project);
+    let json = serde_json::to_string_pretty(&graph).map_err(|e| AnalysisError::Output(e.to_string()))?;
+    if args.to_stdout {
+        println!("{}", json);
+        return Ok(());
+    }
+    fs::create_dir_all(&args.out_dir)
+        .and_then(|_| fs::write(args.out_dir.join("call-graph.json"), json))
+        .map_err(|e| AnalysisError::Output(e.to_string()))
+}
+
+/// Ruta de `path` relativa a `dir`, si está dentro de él.
+fn relative_to(path: &Path, dir: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().ok()?;
+    let dir = dir.canonicalize().ok()?;
+    path.strip_prefix(dir).ok().map(Path::to_path_buf)
+}
+
+
+fn main() {
+    let Some(args) = parse_args() else {
+        eprintln!("Invalid input parameters");
+        eprintln!("{}", USAGE);
+        std::process::exit(1);
+    };
+
+    if let Err(e) = run(args) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -1,10 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use crate::models::function_info::FunctionInfo;
 use crate::models::class_info::ClassInfo;
+use crate::models::documentation::Documentation;
 use crate::models::import_info::ImportInfo;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
+    /// Docstring del módulo.
+    pub documentation: Option<Documentation>,
     pub imports: Vec<ImportInfo>,
     pub functions: Vec<FunctionInfo>,
     pub classes: Vec<ClassInfo>,
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Función, método o clase dentro de un proyecto. `function` es `None`
+/// cuando la referencia apunta a la clase misma (ej: una instanciación).
+/// `qualified_name` distingue símbolos anidados con el mismo nombre
+/// (`externa.<locals>.helper`, `Outer.Inner.metodo`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolRef {
+    pub file: PathBuf,
+    pub class: Option<String>,
+    pub function: Option<String>,
+    pub qualified_name: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CallTarget {
+    Resolved(SymbolRef),
+    /// Módulo importado que no forma parte del proyecto.
+    External { module: String },
+    Builtin,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallEdge {
+    pub caller: SymbolRef,
+    pub callee: CallTarget,
+    /// Nombre llamado, tal como figura en `FunctionCall.name`.
+    pub name: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallGraph {
+    pub edges: Vec<CallEdge>,
+}
+
+impl CallGraph {
+    pub fn resolved(&self) -> impl Iterator<Item = &CallEdge> {
+        self.edges.iter().filter(|e| matches!(e.callee, CallTarget::Resolved(_)))
+    }
+
+    /// Llamadas hechas por el símbolo `qualified_name` de `file`
+    /// (`Clase.metodo`, `externa.<locals>.interna`).
+    pub fn calls_from<'a>(&'a self, file: &'a std::path::Path, qualified_name: &'a str) -> impl Iterator<Item = &'a CallEdge> {
+        self.edges.iter().filter(move |e| e.caller.file == file && e.caller.qualified_name == qualified_name)
+    }
+
+    /// Llamadas resueltas al símbolo `qualified_name` de `file`.
+    pub fn callers_of<'a>(&'a self, file: &'a std::path::Path, qualified_name: &'a str) -> impl Iterator<Item = &'a CallEdge> {
+        self.edges.iter().filter(move |e| match &e.callee {
+            CallTarget::Resolved(target) => target.file == file && target.qualified_name == qualified_name,
+            _ => false,
+        })
+    }
+}
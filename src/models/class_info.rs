@@ -1,9 +1,70 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use crate::models::span::Span;
+use crate::models::decorator::Decorator;
+use crate::models::documentation::Documentation;
 use crate::models::function_info::FunctionInfo;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClassInfo {
     pub name: String,
+    /// Ver `FunctionInfo::qualified_name`.
+    pub qualified_name: String,
+    pub parent: Option<String>,
+    pub full_name: String,
+    pub id: String,
     pub line: usize,
+    pub span: Span,
+    pub bases: Vec<BaseClass>,
+    pub keywords: Vec<ClassKeyword>,
+    pub decorators: Vec<Decorator>,
+    pub documentation: Option<Documentation>,
+    pub fields: Vec<FieldInfo>,
     pub methods: Vec<FunctionInfo>,
-}
\ No newline at end of file
+    /// Clases anidadas en el cuerpo de la clase.
+    pub classes: Vec<ClassInfo>,
+}
+
+/// Clase base (`class User(models.Model)`). `name` es el nombre original
+/// en el módulo `import_name` si viene de un import; `expression` es lo
+/// escrito en la lista de bases (ej: `Generic[T]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseClass {
+    pub name: String,
+    pub expression: String,
+    pub import_name: Option<String>,
+    pub span: Span,
+}
+
+/// Argumento con nombre en la lista de bases (`metaclass=ABCMeta`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassKeyword {
+    pub name: String,
+    pub value: String,
+    pub span: Span,
+}
+
+/// Atributo de datos de una clase: una asignación en el cuerpo de la clase
+/// (`x: int = 0`) o sobre `self`/`cls` dentro de un método
+/// (`self.x = x`), o un campo de una clase de TypeScript/JavaScript
+/// (`static count: number = 0`). `declared_in` es el método donde se asigna
+/// por primera vez; `None` para los declarados en el cuerpo de la clase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldInfo {
+    pub name: String,
+    pub annotation: Option<String>,
+    pub value: Option<String>,
+    pub declared_in: Option<String>,
+    pub kind: FieldKind,
+    pub documentation: Option<Documentation>,
+    pub line: usize,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    /// Compartido por todas las instancias (cuerpo de la clase o `cls.x`).
+    Class,
+    /// Propio de cada instancia (`self.x`).
+    Instance,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use crate::models::span::Span;
+
+/// Decorador de una clase o función (`@dataclass`, `@app.route("/")`).
+/// Como en `FunctionCall`, `name` es el nombre original en el módulo
+/// `import_name` si viene de un import; `expression` es lo escrito tras la
+/// `@`, sin argumentos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Decorator {
+    pub name: String,
+    pub expression: String,
+    pub import_name: Option<String>,
+    pub arguments: Vec<String>,
+    pub line: usize,
+    pub span: Span,
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// Documentación de un módulo, clase, función o atributo: el texto limpio
+/// (sin comillas, asteriscos ni indentación común) y, si se reconoce el
+/// formato, sus partes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Documentation {
+    pub text: String,
+    pub style: DocStyle,
+    /// Primer párrafo del texto.
+    pub summary: Option<String>,
+    pub params: Vec<ParamDoc>,
+    pub returns: Option<ReturnDoc>,
+    pub raises: Vec<RaiseDoc>,
+    /// Motivo de `@deprecated` (vacío si no se indica); `None` si no está
+    /// deprecado.
+    pub deprecated: Option<String>,
+    /// Tags de JSDoc sin campo propio (`@example`, `@see`, `@internal`…).
+    pub tags: Vec<DocTag>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocStyle {
+    /// Texto libre, sin secciones reconocibles.
+    Plain,
+    /// `Args:` / `Returns:` / `Raises:` con entradas indentadas.
+    Google,
+    /// Secciones subrayadas con guiones (`Parameters\n----------`).
+    Numpy,
+    /// Campos de reStructuredText (`:param x:`, `:rtype:`).
+    Rest,
+    /// Comentario `/** ... */` de JSDoc/TSDoc con tags `@param`, `@returns`…
+    Jsdoc,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamDoc {
+    pub name: String,
+    pub param_type: Option<String>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReturnDoc {
+    pub return_type: Option<String>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RaiseDoc {
+    pub exception: String,
+    pub description: String,
+}
+
+/// Tag de JSDoc: `@example` con su contenido, o `@internal` sin valor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocTag {
+    pub name: String,
+    pub value: String,
+}
+
+impl Documentation {
+    /// Documentación de un parámetro por nombre; `*args`, `**kwargs` y
+    /// `...rest` se aceptan con o sin su prefijo.
+    pub fn param(&self, name: &str) -> Option<&ParamDoc> {
+        let unprefixed = |name: &str| name.trim_start_matches(['*', '.']).to_string();
+        let name = unprefixed(name);
+        self.params.iter().find(|p| unprefixed(&p.name) == name)
+    }
+}
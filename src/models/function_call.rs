@@ -1,9 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use crate::models::span::Span;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionCall {
     pub name: String,
     pub line: usize,
+    pub span: Span,
     pub import_name: Option<String>,
-    pub object_name: Option<String>
-}
\ No newline at end of file
+    pub object_name: Option<String>,
+    /// La llamada es el operando de un `await`.
+    pub awaited: bool,
+}
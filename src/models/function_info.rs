@@ -1,13 +1,36 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use crate::models::class_info::ClassInfo;
+use crate::models::decorator::Decorator;
+use crate::models::documentation::Documentation;
+use crate::models::span::Span;
 use crate::models::{function_call::FunctionCall, local_variable::LocalVariable, parameter_info::ParameterInfo};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionInfo {
     pub name: String,
+    /// Nombre dentro del módulo, como `__qualname__` en Python:
+    /// `Clase.metodo`, `externa.<locals>.interna`.
+    pub qualified_name: String,
+    /// `qualified_name` de la clase o función que la contiene.
+    pub parent: Option<String>,
+    /// `qualified_name` precedido del módulo (`paquete.modulo.Clase.metodo`).
+    pub full_name: String,
+    /// Identificador estable derivado de `full_name`.
+    pub id: String,
     pub line: usize,
     pub end_line: usize,
+    pub span: Span,
+    /// `async def` / `async function`.
+    pub is_async: bool,
+    /// Tiene un `yield` propio en Python; `function*` en TypeScript/JavaScript.
+    pub is_generator: bool,
+    pub decorators: Vec<Decorator>,
+    pub documentation: Option<Documentation>,
     pub parameters: Vec<ParameterInfo>,
     pub return_type: Option<String>,
     pub function_calls: Option<Vec<FunctionCall>>,
-    pub local_variables: Vec<LocalVariable>
+    pub local_variables: Vec<LocalVariable>,
+    /// Funciones y clases definidas dentro del cuerpo (closures, helpers).
+    pub functions: Vec<FunctionInfo>,
+    pub classes: Vec<ClassInfo>,
 }
\ No newline at end of file
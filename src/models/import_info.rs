@@ -1,11 +1,116 @@
 use std::path::PathBuf;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use crate::models::span::Span;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportInfo {
     pub name: String,
+    /// Módulo tal como está escrito (`./utils/math`, `.models`); `name` puede
+    /// ser solo su último segmento.
+    pub specifier: String,
+    /// Nombre local del módulo (`import numpy as np` → `np`).
+    pub alias: Option<String>,
+    pub style: ImportStyle,
     pub line: usize,
+    pub span: Span,
     pub path: Option<PathBuf>,
-    pub imported_names: Vec<String>,
-}
\ No newline at end of file
+    /// Regla que produjo `path`; `None` si el import no se resolvió.
+    pub resolved_by: Option<ResolutionRule>,
+    /// Paquete de terceros que contiene `path`; `None` para archivos del
+    /// proyecto.
+    pub package: Option<PackageInfo>,
+    pub imported_names: Vec<ImportedName>,
+}
+
+impl ImportInfo {
+    /// Nombre con el que el módulo queda visible en el archivo.
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Nombre importado que quedó visible como `local` (ej: `b` en
+    /// `from x import a as b`).
+    pub fn imported(&self, local: &str) -> Option<&ImportedName> {
+        self.imported_names.iter().find(|n| n.local_name() == local)
+    }
+}
+
+
+/// Forma sintáctica del import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStyle {
+    /// `import ...` / `from ... import ...`
+    Static,
+    /// `require("...")` de CommonJS
+    Require,
+    /// `import("...")`
+    Dynamic,
+}
+
+
+/// Cómo se encontró el archivo de un import.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum ResolutionRule {
+    /// Relativo al archivo que importa (`./x`, `from . import x`)
+    Relative,
+    /// Debajo de una de las raíces del proyecto
+    ProjectRoot,
+    /// `compilerOptions.baseUrl` del tsconfig
+    BaseUrl,
+    /// Un patrón de `compilerOptions.paths` (ej: `@app/*`)
+    Paths { pattern: String },
+    /// Otro directorio de `compilerOptions.rootDirs`
+    RootDirs,
+    /// Un paquete instalado en `node_modules`
+    NodeModules,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageInfo {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+
+/// Nombre importado desde un módulo (`from x import a as b`,
+/// `import { a as b } from "x"`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportedName {
+    /// Nombre exportado por el módulo: `default` para el export por
+    /// defecto y `*` para un import de namespace.
+    pub name: String,
+    pub alias: Option<String>,
+    pub kind: ImportKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportKind {
+    Named,
+    Default,
+    Namespace,
+}
+
+impl ImportedName {
+    pub fn new(name: impl Into<String>) -> Self {
+        ImportedName { name: name.into(), alias: None, kind: ImportKind::Named }
+    }
+
+    /// `import React from "react"`
+    pub fn default_export(local: impl Into<String>) -> Self {
+        ImportedName { name: "default".to_string(), alias: Some(local.into()), kind: ImportKind::Default }
+    }
+
+    /// `import * as path from "path"`
+    pub fn namespace(local: impl Into<String>) -> Self {
+        ImportedName { name: "*".to_string(), alias: Some(local.into()), kind: ImportKind::Namespace }
+    }
+
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
@@ -1,8 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use crate::models::span::Span;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalVariable {
     pub name: String,
     pub assigned_from: Option<String>,
     pub line: usize,
+    pub span: Span,
 }
\ No newline at end of file
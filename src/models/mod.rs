@@ -4,4 +4,10 @@ pub mod parameter_info;
 pub mod analysis_result;
 pub mod import_info;
 pub mod function_call;
-pub mod local_variable;
\ No newline at end of file
+pub mod local_variable;
+pub mod project_analysis;
+pub mod call_graph;
+pub mod symbol_changes;
+pub mod span;
+pub mod decorator;
+pub mod documentation;
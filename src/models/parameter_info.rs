@@ -1,8 +1,29 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use crate::models::span::Span;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParameterInfo {
     pub name: String,
+    pub kind: ParameterKind,
     pub param_type: Option<String>, 
     pub default_value: Option<String>,
-}
\ No newline at end of file
+    /// Descripción tomada de la documentación de la función.
+    pub description: Option<String>,
+    pub span: Span,
+}
+
+/// Cómo se puede pasar un argumento al parámetro, con la terminología de
+/// `inspect.Parameter` de Python.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterKind {
+    /// Antes de `/`.
+    PositionalOnly,
+    PositionalOrKeyword,
+    /// Después de `*` o `*args`.
+    KeywordOnly,
+    /// `*args`, o `...rest` en TypeScript/JavaScript.
+    VarPositional,
+    /// `**kwargs`.
+    VarKeyword,
+}
@@ -0,0 +1,14 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use crate::models::analysis_result::AnalysisResult;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectAnalysis {
+    pub root: PathBuf,
+    /// Resultados por archivo, con la ruta relativa a `root` como clave.
+    pub files: BTreeMap<PathBuf, AnalysisResult>,
+    /// Archivos soportados que no se pudieron analizar, con el motivo.
+    pub errors: BTreeMap<PathBuf, String>,
+}
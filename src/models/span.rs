@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node;
+
+/// Ubicación de una entidad en el archivo. Las líneas empiezan en 1 (igual
+/// que `line`), las columnas en 0 y se cuentan en bytes, como en Tree-sitter.
+/// `end_*` es exclusivo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Span {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+impl Span {
+    pub fn from_node(node: &Node) -> Self {
+        let start = node.start_position();
+        let end = node.end_position();
+        Span {
+            start_line: start.row + 1,
+            start_column: start.column,
+            end_line: end.row + 1,
+            end_column: end.column,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Símbolos afectados por una edición, identificados por su
+/// `qualified_name` (`funcion`, `Clase.metodo`, `externa.<locals>.interna`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl SymbolChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::fs;
+
+use crate::error::AnalysisError;
+use crate::models::analysis_result::AnalysisResult;
+
+/// Destino de los resultados de análisis. `path` es el archivo analizado.
+pub trait OutputSink {
+    fn write(&mut self, path: &Path, result: &AnalysisResult) -> Result<(), AnalysisError>;
+}
+
+
+/// Escribe un JSON por archivo dentro de `dir`, replicando la ruta del archivo
+/// relativa a `base` (ej: `a/utils.py` -> `<dir>/a/utils.py.json`), de modo que
+/// archivos con el mismo nombre en carpetas distintas no se pisen.
+pub struct DirectorySink {
+    dir: PathBuf,
+    base: Option<PathBuf>,
+    written: HashSet<PathBuf>,
+}
+
+impl DirectorySink {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        DirectorySink { dir: dir.into(), base: None, written: HashSet::new() }
+    }
+
+    pub fn with_base(mut self, base: impl Into<PathBuf>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    pub fn output_path(&self, path: &Path) -> PathBuf {
+        let relative = self.base.as_ref()
+            .and_then(|base| path.strip_prefix(base).ok())
+            .unwrap_or(path);
+
+        // Sin raíz ni `..`, para no escribir fuera de `dir`
+        let mut output = self.dir.clone();
+        for component in relative.components() {
+            match component {
+                Component::Normal(part) => output.push(part),
+                Component::ParentDir => output.push("__parent__"),
+                _ => {}
+            }
+        }
+
+        let file_name = output.file_name()
+            .map(|n| format!("{}.json", n.to_string_lossy()))
+            .unwrap_or_else(|| "output.json".to_string());
+        output.set_file_name(file_name);
+        output
+    }
+
+    /// Archivos escritos por este sink hasta el momento.
+    pub fn written(&self) -> impl Iterator<Item = &PathBuf> {
+        self.written.iter()
+    }
+}
+
+impl OutputSink for DirectorySink {
+    fn write(&mut self, path: &Path, result: &AnalysisResult) -> Result<(), AnalysisError> {
+        let json = to_json(result)?;
+        let output_path = self.output_path(path);
+
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| AnalysisError::Output(format!("could not create {}: {}", parent.display(), e)))?;
+        }
+        fs::write(&output_path, json)
+            .map_err(|e| AnalysisError::Output(format!("could not write {}: {}", output_path.display(), e)))?;
+
+        self.written.insert(output_path);
+        Ok(())
+    }
+}
+
+
+/// Imprime cada resultado como JSON por salida estándar.
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink {
+    fn write(&mut self, _path: &Path, result: &AnalysisResult) -> Result<(), AnalysisError> {
+        let json = to_json(result)?;
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", json).map_err(|e| AnalysisError::Output(e.to_string()))
+    }
+}
+
+
+/// Guarda los resultados en memoria, en el orden en que se escribieron.
+#[derive(Default)]
+pub struct MemorySink {
+    pub results: Vec<(PathBuf, AnalysisResult)>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        MemorySink::default()
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&AnalysisResult> {
+        self.results.iter().find(|(p, _)| p == path).map(|(_, r)| r)
+    }
+}
+
+impl OutputSink for MemorySink {
+    fn write(&mut self, path: &Path, result: &AnalysisResult) -> Result<(), AnalysisError> {
+        self.results.push((path.to_path_buf(), result.clone()));
+        Ok(())
+    }
+}
+
+
+pub fn to_json(result: &AnalysisResult) -> Result<String, AnalysisError> {
+    serde_json::to_string_pretty(result).map_err(|e| AnalysisError::Output(e.to_string()))
+}
@@ -0,0 +1,512 @@
+use crate::models::documentation::{DocStyle, DocTag, Documentation, ParamDoc, RaiseDoc, ReturnDoc};
+use crate::models::parameter_info::ParameterInfo;
+
+/// Secciones de parámetros de Google y NumPy.
+const PARAM_SECTIONS: &[&str] = &[
+    "Args", "Arguments", "Parameters", "Params", "Keyword Args", "Keyword Arguments", "Other Parameters",
+];
+const RETURN_SECTIONS: &[&str] = &["Returns", "Return", "Yields", "Yield"];
+const RAISE_SECTIONS: &[&str] = &["Raises", "Raise", "Exceptions"];
+/// Secciones que se reconocen para separar las demás pero no se extraen.
+const OTHER_SECTIONS: &[&str] = &[
+    "Attributes", "Example", "Examples", "Note", "Notes", "References", "See Also", "Todo", "Warning",
+    "Warnings", "Warns",
+];
+
+/// Campos de reStructuredText (`:param x:`, `:rtype:`).
+const REST_PARAM_FIELDS: &[&str] = &["param", "parameter", "arg", "argument", "key", "keyword"];
+const REST_RETURN_FIELDS: &[&str] = &["returns", "return"];
+const REST_RAISE_FIELDS: &[&str] = &["raises", "raise", "except", "exception"];
+const REST_TYPE_FIELDS: &[&str] = &["type", "rtype"];
+
+/// Tags de JSDoc con campo propio en `Documentation`.
+const JSDOC_PARAM_TAGS: &[&str] = &["param", "arg", "argument"];
+const JSDOC_RETURN_TAGS: &[&str] = &["returns", "return"];
+const JSDOC_THROW_TAGS: &[&str] = &["throws", "exception"];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Params,
+    Returns,
+    Raises,
+    Other,
+}
+
+
+/// Contenido de un literal de string de Python, sin prefijo (`r`, `u`, `b`)
+/// ni comillas.
+pub fn string_contents(literal: &str) -> &str {
+    let unprefixed = literal.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    for quote in ["\"\"\"", "'''", "\"", "'"] {
+        if let Some(inner) = unprefixed.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    unprefixed
+}
+
+
+/// Como `inspect.cleandoc`: la primera línea sin espacios, el resto sin la
+/// indentación común y sin líneas vacías al principio ni al final.
+pub fn clean(raw: &str) -> String {
+    let lines: Vec<&str> = raw.lines().collect();
+    let indent = lines.iter().skip(1)
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| indentation(l))
+        .min()
+        .unwrap_or(0);
+
+    let mut cleaned: Vec<&str> = lines.iter().enumerate()
+        .map(|(i, l)| if i == 0 { l.trim() } else { l.get(indent..).unwrap_or_else(|| l.trim_start()).trim_end() })
+        .collect();
+    while cleaned.first().is_some_and(|l| l.is_empty()) {
+        cleaned.remove(0);
+    }
+    while cleaned.last().is_some_and(|l| l.is_empty()) {
+        cleaned.pop();
+    }
+    cleaned.join("\n")
+}
+
+
+/// Limpia un docstring y extrae sus secciones si está en formato Google,
+/// NumPy o reStructuredText.
+pub fn parse_docstring(raw: &str) -> Documentation {
+    let text = clean(raw);
+    let lines: Vec<&str> = text.lines().collect();
+    let style = detect_style(&lines);
+
+    let mut doc = Documentation {
+        summary: summary(&lines),
+        text: text.clone(),
+        style,
+        params: vec![],
+        returns: None,
+        raises: vec![],
+        deprecated: None,
+        tags: vec![],
+    };
+    match style {
+        DocStyle::Google => parse_google(&lines, &mut doc),
+        DocStyle::Numpy => parse_numpy(&lines, &mut doc),
+        DocStyle::Rest => parse_rest(&lines, &mut doc),
+        DocStyle::Plain | DocStyle::Jsdoc => {}
+    }
+    doc
+}
+
+
+/// Copia a cada parámetro la descripción documentada con su nombre, si
+/// todavía no tiene una.
+pub fn document_parameters(parameters: &mut [ParameterInfo], doc: &Documentation) {
+    for param in parameters.iter_mut().filter(|p| p.description.is_none()) {
+        param.description = doc.param(&param.name)
+            .map(|d| d.description.clone())
+            .filter(|d| !d.is_empty());
+    }
+}
+
+
+fn detect_style(lines: &[&str]) -> DocStyle {
+    if lines.iter().any(|l| rest_field(l).is_some()) {
+        DocStyle::Rest
+    } else if lines.windows(2).any(|w| numpy_header(w[0], w[1]).is_some()) {
+        DocStyle::Numpy
+    } else if lines.iter().any(|l| google_header(l).is_some()) {
+        DocStyle::Google
+    } else {
+        DocStyle::Plain
+    }
+}
+
+/// Primer párrafo, hasta una línea vacía o el comienzo de una sección.
+fn summary(lines: &[&str]) -> Option<String> {
+    let paragraph: Vec<&str> = lines.iter()
+        .take_while(|l| !l.trim().is_empty() && google_header(l).is_none() && rest_field(l).is_none())
+        .map(|l| l.trim())
+        .collect();
+    Some(paragraph.join(" ")).filter(|s| !s.is_empty())
+}
+
+
+fn section_kind(name: &str) -> Option<Section> {
+    if PARAM_SECTIONS.contains(&name) {
+        Some(Section::Params)
+    } else if RETURN_SECTIONS.contains(&name) {
+        Some(Section::Returns)
+    } else if RAISE_SECTIONS.contains(&name) {
+        Some(Section::Raises)
+    } else if OTHER_SECTIONS.contains(&name) {
+        Some(Section::Other)
+    } else {
+        None
+    }
+}
+
+/// `Args:`, `Returns:`…
+fn google_header(line: &str) -> Option<Section> {
+    section_kind(line.trim().strip_suffix(':')?)
+}
+
+/// `Parameters` seguido de una línea de guiones.
+fn numpy_header(line: &str, underline: &str) -> Option<Section> {
+    let underline = underline.trim();
+    if underline.len() < 3 || !underline.chars().all(|c| c == '-') {
+        return None;
+    }
+    section_kind(line.trim())
+}
+
+/// `:param int x: descripción` → (`param`, [`int`, `x`], `descripción`).
+fn rest_field(line: &str) -> Option<(&str, Vec<&str>, &str)> {
+    let (spec, description) = line.trim().strip_prefix(':')?.split_once(':')?;
+    let mut words = spec.split_whitespace();
+    let field = words.next()?;
+    let known = [REST_PARAM_FIELDS, REST_RETURN_FIELDS, REST_RAISE_FIELDS, REST_TYPE_FIELDS]
+        .iter()
+        .any(|fields| fields.contains(&field));
+    known.then(|| (field, words.collect(), description.trim()))
+}
+
+
+/// Agrupa las líneas de una sección en entradas: cada una empieza en una
+/// línea con la indentación mínima de la sección y continúa en las líneas
+/// más indentadas que le siguen.
+fn entries(lines: &[&str]) -> Vec<(String, Vec<String>)> {
+    let Some(base) = lines.iter().filter(|l| !l.trim().is_empty()).map(|l| indentation(l)).min() else {
+        return vec![];
+    };
+
+    let mut entries: Vec<(String, Vec<String>)> = vec![];
+    for line in lines.iter().filter(|l| !l.trim().is_empty()) {
+        if indentation(line) == base {
+            entries.push((line.trim().to_string(), vec![]));
+        } else if let Some((_, continuation)) = entries.last_mut() {
+            continuation.push(line.trim().to_string());
+        }
+    }
+    entries
+}
+
+fn join_description(first: &str, rest: &[String]) -> String {
+    std::iter::once(first.trim())
+        .chain(rest.iter().map(|l| l.as_str()))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `int: El resultado` → (`int`, `El resultado`). Solo se toma como tipo lo
+/// que no tiene espacios (salvo después de una coma, como `Dict[str, int]`).
+fn split_type_prefix(text: &str) -> (Option<String>, String) {
+    if let Some((prefix, rest)) = text.split_once(':') {
+        let prefix = prefix.trim();
+        if !prefix.is_empty() && !prefix.replace(", ", ",").contains(char::is_whitespace) {
+            return (Some(prefix.to_string()), rest.trim().to_string());
+        }
+    }
+    (None, text.trim().to_string())
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+
+// ---------------------------- Google ----------------------------
+
+fn parse_google(lines: &[&str], doc: &mut Documentation) {
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(section) = google_header(lines[i]) else {
+            i += 1;
+            continue;
+        };
+
+        let header_indent = indentation(lines[i]);
+        let start = i + 1;
+        let mut end = start;
+        while end < lines.len() && (lines[end].trim().is_empty() || indentation(lines[end]) > header_indent) {
+            end += 1;
+        }
+
+        let body = &lines[start..end];
+        match section {
+            Section::Params => {
+                for (head, rest) in entries(body) {
+                    let (signature, description) = head.split_once(':').unwrap_or((&head, ""));
+                    // `x (int, optional)` o solo `x`
+                    let (name, param_type) = match signature.split_once('(') {
+                        Some((name, param_type)) => (name.trim(), Some(param_type.trim().trim_end_matches(')').to_string())),
+                        None => (signature.trim(), None),
+                    };
+                    doc.params.push(ParamDoc {
+                        name: name.to_string(),
+                        param_type,
+                        description: join_description(description, &rest),
+                    });
+                }
+            }
+            Section::Returns if doc.returns.is_none() => {
+                let text = body.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ");
+                let (return_type, description) = split_type_prefix(&text);
+                doc.returns = Some(ReturnDoc { return_type, description });
+            }
+            Section::Raises => {
+                for (head, rest) in entries(body) {
+                    let (exception, description) = head.split_once(':').unwrap_or((&head, ""));
+                    doc.raises.push(RaiseDoc {
+                        exception: exception.trim().to_string(),
+                        description: join_description(description, &rest),
+                    });
+                }
+            }
+            _ => {}
+        }
+        i = end;
+    }
+}
+
+
+// ---------------------------- NumPy ----------------------------
+
+fn parse_numpy(lines: &[&str], doc: &mut Documentation) {
+    let is_header = |i: usize| i + 1 < lines.len() && numpy_header(lines[i], lines[i + 1]).is_some();
+
+    let mut i = 0;
+    while i < lines.len() {
+        if !is_header(i) {
+            i += 1;
+            continue;
+        }
+
+        let section = numpy_header(lines[i], lines[i + 1]);
+        let start = i + 2;
+        let mut end = start;
+        while end < lines.len() && !is_header(end) {
+            end += 1;
+        }
+
+        let body = &lines[start..end];
+        match section {
+            Some(Section::Params) => {
+                for (head, rest) in entries(body) {
+                    // `x, y : int`: varios parámetros con la misma descripción
+                    let (names, param_type) = match head.split_once(':') {
+                        Some((names, param_type)) => (names.to_string(), Some(param_type.trim().to_string())),
+                        None => (head.clone(), None),
+                    };
+                    for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                        doc.params.push(ParamDoc {
+                            name: name.to_string(),
+                            param_type: param_type.clone(),
+                            description: join_description("", &rest),
+                        });
+                    }
+                }
+            }
+            Some(Section::Returns) if doc.returns.is_none() => {
+                if let Some((head, rest)) = entries(body).into_iter().next() {
+                    // `int` o `total : int`
+                    let return_type = head.split_once(':').map(|(_, t)| t.trim().to_string()).unwrap_or(head);
+                    doc.returns = Some(ReturnDoc { return_type: Some(return_type), description: join_description("", &rest) });
+                }
+            }
+            Some(Section::Raises) => {
+                for (head, rest) in entries(body) {
+                    doc.raises.push(RaiseDoc { exception: head, description: join_description("", &rest) });
+                }
+            }
+            _ => {}
+        }
+        i = end;
+    }
+}
+
+
+// ---------------------------- reStructuredText ----------------------------
+
+fn parse_rest(lines: &[&str], doc: &mut Documentation) {
+    // Cada campo con sus líneas de continuación (más indentadas)
+    let mut fields: Vec<(&str, Vec<&str>, String)> = vec![];
+    for line in lines {
+        if let Some((field, args, description)) = rest_field(line) {
+            fields.push((field, args, description.to_string()));
+        } else if let Some((_, _, description)) = fields.last_mut()
+            && indentation(line) > 0
+            && !line.trim().is_empty()
+        {
+            description.push(' ');
+            description.push_str(line.trim());
+        }
+    }
+
+    let mut types = vec![];
+    let mut return_type = None;
+    for (field, args, description) in fields {
+        if REST_PARAM_FIELDS.contains(&field) {
+            // `:param x:` o `:param int x:`
+            let Some((name, type_words)) = args.split_last() else { continue };
+            let param_type = Some(type_words.join(" ")).filter(|t| !t.is_empty());
+            doc.params.push(ParamDoc { name: name.to_string(), param_type, description });
+        } else if REST_RETURN_FIELDS.contains(&field) {
+            doc.returns = Some(ReturnDoc { return_type: None, description });
+        } else if REST_RAISE_FIELDS.contains(&field) {
+            doc.raises.push(RaiseDoc { exception: args.join(" "), description });
+        } else if field == "rtype" {
+            return_type = Some(description);
+        } else if let Some(name) = args.first() {
+            types.push((name.to_string(), description));
+        }
+    }
+
+    // `:type x:` y `:rtype:` pueden aparecer antes o después de su campo
+    for (name, param_type) in types {
+        match doc.params.iter_mut().find(|p| p.name == name) {
+            Some(param) => param.param_type = Some(param_type),
+            None => doc.params.push(ParamDoc { name, param_type: Some(param_type), description: String::new() }),
+        }
+    }
+    if let Some(return_type) = return_type {
+        doc.returns.get_or_insert_with(|| ReturnDoc { return_type: None, description: String::new() }).return_type = Some(return_type);
+    }
+}
+
+
+// ---------------------------- JSDoc ----------------------------
+
+/// Limpia un comentario `/** ... */` y extrae la descripción y los tags de
+/// JSDoc/TSDoc. `None` si es un comentario común (`/* */`, `/**/`) o un
+/// separador (`/*****/`).
+pub fn parse_jsdoc(comment: &str) -> Option<Documentation> {
+    let inner = comment.strip_prefix("/**")?.strip_suffix("*/")?;
+    if inner.starts_with('*') {
+        return None;
+    }
+
+    // Sin el `*` al comienzo de cada línea ni el espacio que le sigue; el
+    // resto de la indentación se conserva para los ejemplos de código
+    let mut lines: Vec<&str> = inner.lines()
+        .map(|l| {
+            let l = l.trim_start();
+            let l = l.strip_prefix('*').unwrap_or(l);
+            l.strip_prefix(' ').unwrap_or(l).trim_end()
+        })
+        .collect();
+    while lines.first().is_some_and(|l| l.is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    let (description, tags) = jsdoc_blocks(&lines);
+    let mut doc = Documentation {
+        summary: summary(&description),
+        text: lines.join("\n"),
+        style: DocStyle::Jsdoc,
+        params: vec![],
+        returns: None,
+        raises: vec![],
+        deprecated: None,
+        tags: vec![],
+    };
+
+    for (name, value) in tags {
+        let flat = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        if JSDOC_PARAM_TAGS.contains(&name) {
+            let (param_type, rest) = braced_type(&flat);
+            let Some((name, description)) = jsdoc_param_name(rest) else { continue };
+            doc.params.push(ParamDoc { name, param_type, description });
+        } else if JSDOC_RETURN_TAGS.contains(&name) {
+            let (return_type, rest) = braced_type(&flat);
+            doc.returns = Some(ReturnDoc { return_type, description: strip_hyphen(rest) });
+        } else if JSDOC_THROW_TAGS.contains(&name) {
+            // `@throws {TypeError} si…` o `@throws {@link TypeError}`; sin
+            // llaves el texto es solo descripción
+            let (exception, rest) = braced_type(&flat);
+            let exception = exception.map(|e| e.trim_start_matches("@link").trim().to_string());
+            doc.raises.push(RaiseDoc { exception: exception.unwrap_or_default(), description: strip_hyphen(rest) });
+        } else if name == "deprecated" {
+            doc.deprecated = Some(flat);
+        } else {
+            doc.tags.push(DocTag { name: name.to_string(), value });
+        }
+    }
+    Some(doc)
+}
+
+/// Separa la descripción inicial de los bloques de tags. Cada tag empieza
+/// en una línea con `@nombre` y sigue hasta el próximo; dentro de bloques
+/// de código (```) las líneas con `@` no abren tags.
+fn jsdoc_blocks<'a>(lines: &[&'a str]) -> (Vec<&'a str>, Vec<(&'a str, String)>) {
+    let mut description = vec![];
+    let mut tags: Vec<(&str, String)> = vec![];
+    let mut in_code = false;
+
+    for line in lines {
+        let tag = line.trim_start().strip_prefix('@')
+            .filter(|_| !in_code)
+            .map(|rest| rest.split_at(rest.find(|c: char| c.is_whitespace() || c == '{').unwrap_or(rest.len())))
+            .filter(|(name, _)| name.starts_with(|c: char| c.is_ascii_alphabetic()));
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+
+        match (tag, tags.last_mut()) {
+            (Some((name, value)), _) => tags.push((name, value.trim().to_string())),
+            (None, Some((_, value))) => {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(line);
+            }
+            (None, None) => description.push(*line),
+        }
+    }
+
+    for (_, value) in tags.iter_mut() {
+        *value = value.trim_end().to_string();
+    }
+    (description, tags)
+}
+
+/// `{Array<{a: number}>} resto` → (`Array<{a: number}>`, `resto`).
+fn braced_type(text: &str) -> (Option<String>, &str) {
+    if !text.starts_with('{') {
+        return (None, text);
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return (Some(text[1..i].trim().to_string()), text[i + 1..].trim_start());
+        }
+    }
+    (None, text)
+}
+
+/// `nombre - descripción`, `[nombre=valor] descripción` → (`nombre`,
+/// `descripción`).
+fn jsdoc_param_name(text: &str) -> Option<(String, String)> {
+    let (name, rest) = if let Some(optional) = text.strip_prefix('[') {
+        let (inside, rest) = optional.split_once(']')?;
+        (inside.split('=').next().unwrap_or(inside).trim(), rest)
+    } else {
+        text.split_once(char::is_whitespace).unwrap_or((text, ""))
+    };
+    if name.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), strip_hyphen(rest)))
+}
+
+/// TSDoc separa el nombre de la descripción con un guion.
+fn strip_hyphen(text: &str) -> String {
+    let text = text.trim();
+    text.strip_prefix("- ").unwrap_or(text).trim().to_string()
+}
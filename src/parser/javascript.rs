@@ -0,0 +1,49 @@
+use std::path::{Path, PathBuf};
+use tree_sitter::{Language, Node, Tree};
+use crate::models::analysis_result::AnalysisResult;
+use crate::error::AnalysisError;
+use crate::parser::language::LanguageAnalyzer;
+use crate::parser::pool;
+use crate::parser::typescript::{self, TypeScriptAnalyzer};
+
+/// Backend para JavaScript. Usa la gramática de JavaScript (que incluye JSX)
+/// para no interpretar como TypeScript código que no lo es, y comparte la
+/// extracción con `typescript.rs`: los nodos que no existen en una u otra
+/// gramática simplemente no aparecen.
+pub struct JavaScriptAnalyzer;
+
+impl LanguageAnalyzer for JavaScriptAnalyzer {
+    fn name(&self) -> &str {
+        "javascript"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["js", "jsx", "mjs", "cjs"]
+    }
+
+    fn language(&self, _extension: &str) -> Language {
+        tree_sitter_javascript::language()
+    }
+
+    fn analyze(&self, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        parse(source, path, root_path)
+    }
+
+    fn analyze_tree(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        typescript::analyze_tree(tree, source, path, root_path)
+    }
+
+    fn analyze_tree_filtered(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf], extract: &dyn Fn(&Node) -> bool) -> Result<AnalysisResult, AnalysisError> {
+        typescript::analyze_tree_filtered(tree, source, path, root_path, extract)
+    }
+
+    fn is_builtin(&self, name: &str) -> bool {
+        TypeScriptAnalyzer.is_builtin(name)
+    }
+}
+
+
+pub fn parse(source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+    let tree = pool::parse_tree(tree_sitter_javascript::language(), source, None, path)?;
+    typescript::analyze_tree(&tree, source, path, root_path)
+}
@@ -0,0 +1,42 @@
+use std::path::{Path, PathBuf};
+
+use tree_sitter::{Language, Node, Tree};
+use crate::error::AnalysisError;
+use crate::models::analysis_result::AnalysisResult;
+
+/// Backend de análisis para un lenguaje. Cada implementación declara las
+/// extensiones que maneja y la gramática de Tree-sitter a usar por extensión.
+pub trait LanguageAnalyzer: Send + Sync {
+    /// Nombre legible del lenguaje (ej: "python").
+    fn name(&self) -> &str;
+
+    /// Extensiones de archivo soportadas, sin el punto.
+    fn extensions(&self) -> &[&str];
+
+    /// Gramática de Tree-sitter para la extensión dada.
+    fn language(&self, extension: &str) -> Language;
+
+    fn analyze(&self, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError>;
+
+    /// Extrae el análisis de un árbol ya parseado con `language`. Lo usa
+    /// `AnalysisSession` para reutilizar el árbol entre ediciones; la
+    /// implementación por defecto vuelve a parsear desde `source`.
+    fn analyze_tree(&self, _tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        self.analyze(source, path, root_path)
+    }
+
+    /// Como `analyze_tree`, pero solo extrae funciones y clases de los nodos
+    /// de nivel superior para los que `extract` devuelve `true`; imports y
+    /// docstring del módulo se extraen siempre completos. Lo usa
+    /// `AnalysisSession` para no volver a extraer lo que una edición no
+    /// tocó. La implementación por defecto extrae todo.
+    fn analyze_tree_filtered(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf], _extract: &dyn Fn(&Node) -> bool) -> Result<AnalysisResult, AnalysisError> {
+        self.analyze_tree(tree, source, path, root_path)
+    }
+
+    /// Si `name` es una función u objeto global del lenguaje (ej: `print`,
+    /// `console`). Lo usa el grafo de llamadas para etiquetar esas llamadas.
+    fn is_builtin(&self, _name: &str) -> bool {
+        false
+    }
+}
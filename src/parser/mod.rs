@@ -1,15 +1,27 @@
+
+pub mod language;
+pub mod registry;
+pub mod pool;
 pub mod python;
 pub mod typescript;
+pub mod javascript;
+pub mod tsconfig;
+pub mod node_modules;
+pub mod symbols;
+pub mod docstrings;
+pub mod probe;
+mod scope;
 
 use std::path::{Path, PathBuf};
+use crate::error::AnalysisError;
 use crate::models::analysis_result::AnalysisResult;
 
-#[allow(dead_code)]
-pub fn parse_file(source: &str, path: &Path, root_path: &[PathBuf]) -> AnalysisResult {
-    match path.extension().and_then(|e| e.to_str()) {
-        Some("py") => python::parse(source, path, root_path),
-        Some("ts") | Some("js") => typescript::parse(source, path, root_path, false),
-        Some("tsx") | Some("jsx") => typescript::parse(source, path, root_path, true),
-        _ => panic!("Unsupported file type"),
+pub use language::LanguageAnalyzer;
+pub use registry::{register_language, registry, supported_extensions, supported_languages, LanguageRegistry};
+
+pub fn parse_file(source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+    match registry::analyzer_for_path(path) {
+        Some(analyzer) => analyzer.analyze(source, path, root_path),
+        None => Err(AnalysisError::UnsupportedLanguage(path.to_path_buf())),
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::models::import_info::PackageInfo;
+use crate::parser::probe;
+
+/// Condiciones de `exports` que se aceptan, en orden de preferencia. Los
+/// tipos primero, como hace TypeScript.
+const EXPORT_CONDITIONS: &[&str] = &["types", "typings", "import", "module", "require", "node", "default"];
+
+/// Campos de `package.json` que apuntan a la entrada del paquete cuando no
+/// hay `exports`.
+const ENTRY_FIELDS: &[&str] = &["types", "typings", "module", "main"];
+
+/// Resuelve un especificador sin ruta (`lodash`, `@scope/pkg/sub`) buscando
+/// `node_modules` desde `from_dir` hacia arriba, como Node. `find_module`
+/// completa extensiones e `index.*` para rutas sin archivo exacto.
+pub fn resolve_package(
+    from_dir: &Path,
+    specifier: &str,
+    find_module: impl Fn(&Path) -> Option<PathBuf>,
+) -> Option<(PathBuf, PackageInfo)> {
+    let (package_name, subpath) = split_specifier(specifier)?;
+
+    let package_dir = from_dir.ancestors()
+        .map(|dir| dir.join("node_modules").join(package_name))
+        .find(|dir| probe::is_dir(dir))?;
+    let manifest: Option<Value> = probe::read_to_string(&package_dir.join("package.json")).ok()
+        .and_then(|text| serde_json::from_str(&text).ok());
+
+    let package = PackageInfo {
+        name: manifest.as_ref()
+            .and_then(|m| m.get("name")).and_then(Value::as_str)
+            .unwrap_or(package_name)
+            .to_string(),
+        version: manifest.as_ref()
+            .and_then(|m| m.get("version")).and_then(Value::as_str)
+            .map(|v| v.to_string()),
+    };
+
+    let existing = |target: &str| -> Option<PathBuf> {
+        let path = package_dir.join(target);
+        if probe::is_file(&path) {
+            return path.canonicalize().ok();
+        }
+        find_module(&path)
+    };
+
+    // Con `exports`, solo lo exportado es visible desde afuera del paquete
+    if let Some(exports) = manifest.as_ref().and_then(|m| m.get("exports")) {
+        let export_key = if subpath.is_empty() { ".".to_string() } else { format!("./{}", subpath) };
+        return export_targets(exports, &export_key).iter()
+            .find_map(|target| existing(target))
+            .map(|found| (found, package));
+    }
+
+    if !subpath.is_empty() {
+        return existing(subpath).map(|found| (found, package));
+    }
+
+    let entry = ENTRY_FIELDS.iter()
+        .filter_map(|field| manifest.as_ref()?.get(*field)?.as_str())
+        .find_map(existing);
+    entry.or_else(|| existing("index")).map(|found| (found, package))
+}
+
+
+/// `@scope/pkg/sub/path` → (`@scope/pkg`, `sub/path`); `lodash` → (`lodash`, ``).
+fn split_specifier(specifier: &str) -> Option<(&str, &str)> {
+    let name_len = if specifier.starts_with('@') {
+        let scope_end = specifier.find('/')?;
+        let rest = &specifier[scope_end + 1..];
+        scope_end + 1 + rest.find('/').unwrap_or(rest.len())
+    } else {
+        specifier.find('/').unwrap_or(specifier.len())
+    };
+
+    let (name, subpath) = specifier.split_at(name_len);
+    if name.is_empty() || name.ends_with('/') {
+        return None;
+    }
+    Some((name, subpath.trim_start_matches('/')))
+}
+
+
+/// Destinos candidatos de `exports` para `key` (`.` o `./sub`), en orden
+/// de preferencia.
+fn export_targets(exports: &Value, key: &str) -> Vec<String> {
+    let subpaths = exports.as_object().filter(|map| map.keys().any(|k| k.starts_with('.')));
+
+    let Some(subpaths) = subpaths else {
+        // Forma corta: `"exports": "./index.js"` o solo condiciones
+        return if key == "." { condition_targets(exports, None) } else { vec![] };
+    };
+
+    if let Some(value) = subpaths.get(key) {
+        return condition_targets(value, None);
+    }
+
+    // Patrones `./*` o `./features/*.js`: gana el de prefijo más largo
+    subpaths.iter()
+        .filter_map(|(pattern, value)| {
+            let (prefix, suffix) = pattern.split_once('*')?;
+            let captured = key.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), value, captured))
+        })
+        .max_by_key(|(prefix_len, _, _)| *prefix_len)
+        .map(|(_, value, captured)| condition_targets(value, Some(captured)))
+        .unwrap_or_default()
+}
+
+fn condition_targets(value: &Value, captured: Option<&str>) -> Vec<String> {
+    match value {
+        Value::String(target) => vec![match captured {
+            Some(captured) => target.replace('*', captured),
+            None => target.clone(),
+        }],
+        Value::Array(alternatives) => alternatives.iter()
+            .flat_map(|alternative| condition_targets(alternative, captured))
+            .collect(),
+        Value::Object(conditions) => EXPORT_CONDITIONS.iter()
+            .filter_map(|condition| conditions.get(*condition))
+            .flat_map(|target| condition_targets(target, captured))
+            .collect(),
+        _ => vec![],
+    }
+}
@@ -0,0 +1,35 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+
+use tree_sitter::{Language, Parser, Tree};
+use crate::error::AnalysisError;
+
+thread_local! {
+    // Un parser por gramática y por hilo: `set_language` solo se paga la primera vez
+    static PARSERS: RefCell<HashMap<Language, Parser>> = RefCell::new(HashMap::new());
+}
+
+/// Parsea `source` reutilizando el parser del hilo actual para `language`.
+pub fn parse_tree(language: Language, source: &str, old_tree: Option<&Tree>, path: &Path) -> Result<Tree, AnalysisError> {
+    // Se saca el parser del mapa mientras se usa, así un parseo anidado en el
+    // mismo hilo crea uno propio en lugar de fallar por doble préstamo.
+    let cached = PARSERS.with(|parsers| parsers.borrow_mut().remove(&language));
+    let mut parser = match cached {
+        Some(parser) => parser,
+        None => {
+            let mut parser = Parser::new();
+            parser.set_language(language)
+                .map_err(|e| AnalysisError::Parse { path: path.to_path_buf(), message: e.to_string() })?;
+            parser
+        }
+    };
+
+    let tree = parser.parse(source, old_tree);
+    if tree.is_none() {
+        parser.reset();
+    }
+    PARSERS.with(|parsers| parsers.borrow_mut().insert(language, parser));
+
+    tree.ok_or_else(|| AnalysisError::Parse { path: path.to_path_buf(), message: "parser returned no tree".to_string() })
+}
@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+thread_local! {
+    static RECORDED: RefCell<Option<Vec<Probe>>> = const { RefCell::new(None) };
+}
+
+/// Consulta al sistema de archivos hecha al resolver un import: qué ruta se
+/// miró y qué se encontró. El caché guarda las consultas de cada archivo y
+/// descarta la entrada solo si alguna daría hoy otro resultado.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Probe {
+    pub path: PathBuf,
+    pub state: PathState,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathState {
+    Missing,
+    File,
+    Dir,
+    /// Archivo leído (`tsconfig.json`, `package.json`), con el hash de su
+    /// contenido.
+    Content(String),
+}
+
+impl Probe {
+    /// Si la ruta sigue en el mismo estado que cuando se consultó.
+    pub fn is_current(&self) -> bool {
+        let state = match self.state {
+            PathState::Content(_) => content_state(&self.path),
+            _ => path_state(&self.path),
+        };
+        state == self.state
+    }
+}
+
+
+/// Ejecuta `f` registrando las consultas que hagan, en este hilo, las
+/// funciones de este módulo.
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Probe>) {
+    let previous = RECORDED.with(|r| r.borrow_mut().replace(vec![]));
+    let value = f();
+    let mut probes = RECORDED.with(|r| std::mem::replace(&mut *r.borrow_mut(), previous)).unwrap_or_default();
+    probes.sort();
+    probes.dedup();
+    (value, probes)
+}
+
+/// Vuelve a registrar consultas hechas antes, para un resultado reutilizado
+/// que no vuelve a mirar el sistema de archivos.
+pub fn replay(probes: &[Probe]) {
+    RECORDED.with(|r| {
+        if let Some(recorded) = r.borrow_mut().as_mut() {
+            recorded.extend_from_slice(probes);
+        }
+    });
+}
+
+pub fn is_file(path: &Path) -> bool {
+    observe(path, path_state(path)) == PathState::File
+}
+
+pub fn is_dir(path: &Path) -> bool {
+    observe(path, path_state(path)) == PathState::Dir
+}
+
+pub fn exists(path: &Path) -> bool {
+    observe(path, path_state(path)) != PathState::Missing
+}
+
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    let text = fs::read_to_string(path);
+    let state = match &text {
+        Ok(text) => PathState::Content(hash(text.as_bytes())),
+        Err(_) => PathState::Missing,
+    };
+    observe(path, state);
+    text
+}
+
+
+fn observe(path: &Path, state: PathState) -> PathState {
+    RECORDED.with(|r| {
+        if let Some(probes) = r.borrow_mut().as_mut() {
+            probes.push(Probe { path: path.to_path_buf(), state: state.clone() });
+        }
+    });
+    state
+}
+
+fn path_state(path: &Path) -> PathState {
+    match fs::metadata(path) {
+        Ok(meta) if meta.is_dir() => PathState::Dir,
+        Ok(_) => PathState::File,
+        Err(_) => PathState::Missing,
+    }
+}
+
+fn content_state(path: &Path) -> PathState {
+    match fs::read(path) {
+        Ok(content) => PathState::Content(hash(&content)),
+        Err(_) => PathState::Missing,
+    }
+}
+
+fn hash(content: &[u8]) -> String {
+    Sha256::digest(content).iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -1,50 +1,118 @@
 #![allow(dead_code)]
 
 use std::path::{Path,PathBuf};
+use std::str::Utf8Error;
 
-use tree_sitter::{Parser, TreeCursor, Node};
+use tree_sitter::{Language, Tree, TreeCursor, Node};
 use crate::models::function_call::FunctionCall;
-use crate::models::import_info::ImportInfo;
+use crate::models::import_info::{ImportInfo, ImportKind, ImportStyle, ImportedName, ResolutionRule};
 use crate::models::local_variable::LocalVariable;
-use crate::models::{analysis_result::AnalysisResult, class_info::ClassInfo, function_info::FunctionInfo, parameter_info::ParameterInfo};
+use crate::models::span::Span;
+use crate::models::{analysis_result::AnalysisResult, class_info::{BaseClass, ClassInfo, ClassKeyword, FieldInfo, FieldKind}, decorator::Decorator, documentation::Documentation, function_info::FunctionInfo, parameter_info::{ParameterInfo, ParameterKind}};
+use crate::error::AnalysisError;
+use crate::parser::language::LanguageAnalyzer;
+use crate::parser::pool;
+use crate::parser::docstrings;
+use crate::parser::probe;
+use crate::parser::scope::Scope;
+use crate::parser::symbols;
+
+pub struct PythonAnalyzer;
+
+impl LanguageAnalyzer for PythonAnalyzer {
+    fn name(&self) -> &str {
+        "python"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["py"]
+    }
+
+    fn language(&self, _extension: &str) -> Language {
+        tree_sitter_python::language()
+    }
+
+    fn analyze(&self, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        parse(source, path, root_path)
+    }
+
+    fn analyze_tree(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        analyze_tree(tree, source, path, root_path)
+    }
+
+    fn analyze_tree_filtered(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf], extract: &dyn Fn(&Node) -> bool) -> Result<AnalysisResult, AnalysisError> {
+        analyze_tree_filtered(tree, source, path, root_path, extract)
+    }
 
-pub fn parse(source: &str, path: &Path, root_path: &[PathBuf]) -> AnalysisResult {
-    let mut parser = Parser::new();
-    parser.set_language(tree_sitter_python::language()).unwrap();
-    let tree = parser.parse(source, None).unwrap();
+    fn is_builtin(&self, name: &str) -> bool {
+        PYTHON_BUILTINS.contains(&name)
+    }
+}
+
+const PYTHON_BUILTINS: &[&str] = &[
+    "abs", "aiter", "all", "anext", "any", "ascii", "bin", "bool", "breakpoint", "bytearray", "bytes",
+    "callable", "chr", "classmethod", "compile", "complex", "delattr", "dict", "dir", "divmod",
+    "enumerate", "eval", "exec", "filter", "float", "format", "frozenset", "getattr", "globals",
+    "hasattr", "hash", "help", "hex", "id", "input", "int", "isinstance", "issubclass", "iter", "len",
+    "list", "locals", "map", "max", "memoryview", "min", "next", "object", "oct", "open", "ord", "pow",
+    "print", "property", "range", "repr", "reversed", "round", "set", "setattr", "slice", "sorted",
+    "staticmethod", "str", "sum", "super", "tuple", "type", "vars", "zip", "__import__",
+];
+
+pub fn parse(source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+    let tree = pool::parse_tree(PythonAnalyzer.language("py"), source, None, path)?;
+    analyze_tree(&tree, source, path, root_path)
+}
+
+
+pub fn analyze_tree(tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+    analyze_tree_filtered(tree, source, path, root_path, &|_| true)
+}
+
+/// Ver `LanguageAnalyzer::analyze_tree_filtered`.
+pub fn analyze_tree_filtered(tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf], extract: &dyn Fn(&Node) -> bool) -> Result<AnalysisResult, AnalysisError> {
     let root_node = tree.root_node();
 
     // print_tree(source, root_node, 0);
 
+    let documentation = docstring(source, &root_node)
+        .map_err(|_| AnalysisError::InvalidUtf8(path.to_path_buf()))?;
+
     let mut result = AnalysisResult {
+        documentation,
         imports: vec![],
         functions: vec![],
         classes: vec![],
     };
-    let mut none_class: Option<&mut ClassInfo> = None;
-    analyze_node(path, root_path, source, &mut root_node.walk(), &mut result, &mut none_class);
+    // Los imports de los nodos que no se extraen se recorren igual: las
+    // llamadas de las funciones siguientes se resuelven con ellos
+    let mut cursor = root_node.walk();
+    for node in root_node.children(&mut cursor) {
+        let walked = if extract(&node) {
+            analyze_node(path, root_path, source, &mut node.walk(), &mut result, &mut Scope::Module)
+        } else {
+            collect_imports(path, root_path, source, &node, &mut result.imports)
+        };
+        walked.map_err(|_| AnalysisError::InvalidUtf8(path.to_path_buf()))?;
+    }
+    symbols::assign_symbol_ids(&mut result, &symbols::module_name(path, root_path));
 
-    result
+    Ok(result)
 }
 
 
-fn analyze_node(path: &Path, root_path: &[PathBuf], source: &str, cursor: &mut TreeCursor, result: &mut AnalysisResult, current_class: &mut Option<&mut ClassInfo>) {
+fn analyze_node(path: &Path, root_path: &[PathBuf], source: &str, cursor: &mut TreeCursor, result: &mut AnalysisResult, scope: &mut Scope) -> Result<(), Utf8Error> {
     loop {
         let node = cursor.node();
         let kind = node.kind();
 
         match kind {
             "import_statement" => {
-                let text = node.utf8_text(source.as_bytes()).unwrap_or("").trim().to_string();
-                let parts: Vec<&str> = text.split(' ').collect();
-                let field_name: String = parts.get(1).unwrap().to_string();
-
-                let import_path = resolve_python_import(path, &field_name, root_path);
-
-                result.imports.push(ImportInfo { name: field_name, line: node.start_position().row + 1, path: import_path, imported_names: vec![] });
+                let imports = parse_import_statement(source, &node, path, root_path)?;
+                result.imports.extend(imports);
             }
             "import_from_statement" => {
-                let import_from = parse_import_from_statement(source, &node, path, root_path);
+                let import_from = parse_import_from_statement(source, &node, path, root_path)?;
                 result.imports.push(import_from);
             }
             "function_definition" => {
@@ -54,17 +122,17 @@ fn analyze_node(path: &Path, root_path: &[PathBuf], source: &str, cursor: &mut T
                     .unwrap_or("<unnamed>")
                     .to_string();
             
-                let parameters = get_function_parameters(source, &node);
+                let mut parameters = get_function_parameters(source, &node)?;
 
                 let mut return_type: Option<String> = None;
 
                 if let Some(node_return_type) = node.child_by_field_name("return_type") {
-                    return_type = Some(node_return_type.utf8_text(source.as_bytes()).unwrap().to_string());
+                    return_type = Some(node_return_type.utf8_text(source.as_bytes())?.to_string());
                 }
 
                 let mut function_calls: Option<Vec<FunctionCall>> = None;
                 if let Some(node_return_body) = node.child_by_field_name("body") {
-                    let calls = find_calls(source, &node_return_body, &result.imports);
+                    let calls = find_calls(source, &node_return_body, &result.imports)?;
                     function_calls = Some(calls);
                 }   
 
@@ -73,21 +141,44 @@ fn analyze_node(path: &Path, root_path: &[PathBuf], source: &str, cursor: &mut T
                     local_variables = find_local_variables(source, &node_body);
                 }  
             
-                let func_info = FunctionInfo {
+                let decorators = parse_decorators(source, &node, &result.imports)?;
+                let (qualified_name, parent) = scope.qualify(&name);
+
+                let documentation = match node.child_by_field_name("body") {
+                    Some(body) => docstring(source, &body)?,
+                    None => None,
+                };
+                if let Some(doc) = &documentation {
+                    docstrings::document_parameters(&mut parameters, doc);
+                }
+
+                let mut func_info = FunctionInfo {
                     name,
+                    qualified_name,
+                    parent,
+                    full_name: String::new(),
+                    id: String::new(),
                     line: node.start_position().row + 1,
                     end_line: node.end_position().row + 1,
+                    span: Span::from_node(&node),
+                    is_async: node.children(&mut node.walk()).any(|c| c.kind() == "async"),
+                    is_generator: node.child_by_field_name("body").is_some_and(|body| contains_yield(&body)),
+                    decorators,
+                    documentation,
                     parameters,
-                    return_type: return_type,
+                    return_type,
                     function_calls,
-                    local_variables
+                    local_variables,
+                    functions: vec![],
+                    classes: vec![],
                 };
-            
-                if let Some(class) = current_class.as_deref_mut() {
-                    class.methods.push(func_info);
-                } else {
-                    result.functions.push(func_info);
+
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut inner_cursor = body.walk();
+                    analyze_node(path, root_path, source, &mut inner_cursor, result, &mut Scope::Function(&mut func_info))?;
                 }
+
+                scope.push_function(result, func_info);
             }
             "class_definition" => {
                 let name = node
@@ -96,25 +187,52 @@ fn analyze_node(path: &Path, root_path: &[PathBuf], source: &str, cursor: &mut T
                     .unwrap_or("<unnamed>")
                     .to_string();
             
+                let (bases, keywords) = parse_superclasses(source, &node, &result.imports)?;
+                let decorators = parse_decorators(source, &node, &result.imports)?;
+                let (qualified_name, parent) = scope.qualify(&name);
+                let documentation = match node.child_by_field_name("body") {
+                    Some(body) => docstring(source, &body)?,
+                    None => None,
+                };
+
                 let mut class_info = ClassInfo {
                     name,
+                    qualified_name,
+                    parent,
+                    full_name: String::new(),
+                    id: String::new(),
                     line: node.start_position().row + 1,
+                    span: Span::from_node(&node),
+                    bases,
+                    keywords,
+                    decorators,
+                    documentation,
+                    fields: vec![],
                     methods: Vec::new(),
+                    classes: vec![],
                 };
             
                 if let Some(body) = node.child_by_field_name("body") {
+                    class_info.fields = parse_class_fields(source, &body)?;
                     let mut inner_cursor = body.walk();
-                    let mut class_ref = Some(&mut class_info);
-                    analyze_node(path, root_path, source, &mut inner_cursor, result, &mut class_ref);
+                    analyze_node(path, root_path, source, &mut inner_cursor, result, &mut Scope::Class(&mut class_info))?;
                 }
-            
-                result.classes.push(class_info);
+
+                // Los argumentos del constructor suelen documentarse en la clase
+                if let Some(doc) = &class_info.documentation
+                    && let Some(init) = class_info.methods.iter_mut().find(|m| m.name == "__init__")
+                {
+                    docstrings::document_parameters(&mut init.parameters, doc);
+                }
+
+                scope.push_class(result, class_info);
             }
             _ => {}
         }
 
-        if kind != "class_definition" && cursor.goto_first_child() {
-            analyze_node(path, root_path, source, cursor, result, current_class);
+        // Los cuerpos de clases y funciones ya se recorrieron con su ámbito
+        if kind != "class_definition" && kind != "function_definition" && cursor.goto_first_child() {
+            analyze_node(path, root_path, source, cursor, result, scope)?;
             cursor.goto_parent();
         }
 
@@ -122,6 +240,73 @@ fn analyze_node(path: &Path, root_path: &[PathBuf], source: &str, cursor: &mut T
             break;
         }
     }
+
+    Ok(())
+}
+
+
+/// Imports de `node` y sus descendientes, en orden de aparición.
+fn collect_imports(path: &Path, root_path: &[PathBuf], source: &str, node: &Node, imports: &mut Vec<ImportInfo>) -> Result<(), Utf8Error> {
+    match node.kind() {
+        "import_statement" => imports.extend(parse_import_statement(source, node, path, root_path)?),
+        "import_from_statement" => imports.push(parse_import_from_statement(source, node, path, root_path)?),
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                collect_imports(path, root_path, source, &child, imports)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+
+/// `import a`, `import a.b as c`, `import sys, os`: un `ImportInfo` por módulo.
+fn parse_import_statement(
+    source: &str,
+    node: &Node,
+    current_file: &Path,
+    project_roots: &[PathBuf],
+) -> Result<Vec<ImportInfo>, Utf8Error> {
+    let mut imports = vec![];
+
+    let mut cursor = node.walk();
+    for child in node.children_by_field_name("name", &mut cursor) {
+        let Some(imported) = parse_imported_name(source, &child)? else { continue };
+        let import_path = resolve_python_import(current_file, &imported.name, project_roots);
+        let resolved_by = import_path.as_ref().map(|_| python_rule(&imported.name));
+
+        imports.push(ImportInfo {
+            specifier: imported.name.clone(),
+            name: imported.name,
+            alias: imported.alias,
+            style: ImportStyle::Static,
+            line: node.start_position().row + 1,
+            span: Span::from_node(node),
+            path: import_path,
+            resolved_by,
+            package: None,
+            imported_names: vec![],
+        });
+    }
+
+    Ok(imports)
+}
+
+
+/// `dotted_name` o `aliased_import` (`a.b as c`).
+fn parse_imported_name(source: &str, node: &Node) -> Result<Option<ImportedName>, Utf8Error> {
+    match node.kind() {
+        "dotted_name" | "identifier" => Ok(Some(ImportedName::new(node.utf8_text(source.as_bytes())?))),
+        "aliased_import" => {
+            let Some(name) = node.child_by_field_name("name") else { return Ok(None) };
+            let alias = node.child_by_field_name("alias")
+                .map(|n| n.utf8_text(source.as_bytes()).map(|s| s.to_string()))
+                .transpose()?;
+            Ok(Some(ImportedName { name: name.utf8_text(source.as_bytes())?.to_string(), alias, kind: ImportKind::Named }))
+        }
+        _ => Ok(None),
+    }
 }
 
 
@@ -130,7 +315,7 @@ fn parse_import_from_statement(
     node: &tree_sitter::Node,
     current_file: &Path,
     project_roots: &[PathBuf],
-) -> ImportInfo {
+) -> Result<ImportInfo, Utf8Error> {
     let mut functions = vec![];
 
     let module_node = node.child_by_field_name("module_name");
@@ -144,6 +329,7 @@ fn parse_import_from_statement(
     }
 
     let import_path = resolve_python_import(current_file, &file_name, project_roots);
+    let resolved_by = import_path.as_ref().map(|_| python_rule(&file_name));
 
     let mut cursor = node.walk();
 
@@ -151,38 +337,275 @@ fn parse_import_from_statement(
     for child in node.children(&mut cursor) {
         match child.kind() {
             "import" => { past_import_keyword = true; }
-            "dotted_name" | "identifier" if past_import_keyword => {
-                let text = child.utf8_text(source.as_bytes()).unwrap().to_string();
-                functions.push(text);
+            "dotted_name" | "identifier" | "aliased_import" if past_import_keyword => {
+                if let Some(imported) = parse_imported_name(source, &child)? {
+                    functions.push(imported);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ImportInfo { specifier: file_name.clone(), name: file_name, alias: None, style: ImportStyle::Static, line: node.start_position().row + 1, span: Span::from_node(node), resolved_by, package: None, path: import_path, imported_names: functions })
+}
+
+
+/// Docstring de un módulo, clase o función: el primer statement de su
+/// cuerpo, si es un string literal (no un f-string).
+fn docstring(source: &str, body: &Node) -> Result<Option<Documentation>, Utf8Error> {
+    let Some(first) = body.named_children(&mut body.walk()).find(|c| c.kind() != "comment") else {
+        return Ok(None);
+    };
+    if first.kind() != "expression_statement" || first.named_child_count() != 1 {
+        return Ok(None);
+    }
+    let Some(literal) = first.named_child(0).filter(|n| n.kind() == "string") else {
+        return Ok(None);
+    };
+
+    let text = literal.utf8_text(source.as_bytes())?;
+    if text.chars().take_while(|c| c.is_ascii_alphabetic()).any(|c| c == 'f' || c == 'F') {
+        return Ok(None);
+    }
+    Ok(Some(docstrings::parse_docstring(docstrings::string_contents(text))))
+}
+
+
+/// Bases y argumentos con nombre de `class A(Base, metaclass=Meta)`.
+fn parse_superclasses(source: &str, node: &Node, imports: &[ImportInfo]) -> Result<(Vec<BaseClass>, Vec<ClassKeyword>), Utf8Error> {
+    let mut bases = vec![];
+    let mut keywords = vec![];
+    let Some(arguments) = node.child_by_field_name("superclasses") else {
+        return Ok((bases, keywords));
+    };
+
+    let mut cursor = arguments.walk();
+    for child in arguments.named_children(&mut cursor) {
+        match child.kind() {
+            "keyword_argument" => {
+                let (Some(name), Some(value)) = (child.child_by_field_name("name"), child.child_by_field_name("value")) else { continue };
+                keywords.push(ClassKeyword {
+                    name: name.utf8_text(source.as_bytes())?.to_string(),
+                    value: value.utf8_text(source.as_bytes())?.to_string(),
+                    span: Span::from_node(&child),
+                });
+            }
+            "comment" | "list_splat" | "dictionary_splat" => {}
+            _ => {
+                let expression = child.utf8_text(source.as_bytes())?.to_string();
+                // `Generic[T]`, `Base[int]`: la clase es lo que se subíndica
+                let target = if child.kind() == "subscript" { child.child_by_field_name("value") } else { Some(child) };
+                let (name, import_name) = match target.filter(is_dotted_name) {
+                    Some(target) => resolve_reference(imports, target.utf8_text(source.as_bytes())?),
+                    None => (expression.clone(), None),
+                };
+                bases.push(BaseClass { name, expression, import_name, span: Span::from_node(&child) });
+            }
+        }
+    }
+
+    Ok((bases, keywords))
+}
+
+
+/// Decoradores de una función o clase, que cuelgan del
+/// `decorated_definition` que la envuelve.
+fn parse_decorators(source: &str, node: &Node, imports: &[ImportInfo]) -> Result<Vec<Decorator>, Utf8Error> {
+    let mut decorators = vec![];
+    let Some(parent) = node.parent().filter(|p| p.kind() == "decorated_definition") else {
+        return Ok(decorators);
+    };
+
+    let mut cursor = parent.walk();
+    for decorator in parent.named_children(&mut cursor).filter(|c| c.kind() == "decorator") {
+        let Some(mut callee) = decorator.named_child(0) else { continue };
+
+        // `@app.route("/", methods=["GET"])`
+        let mut arguments = vec![];
+        if callee.kind() == "call" {
+            if let Some(args) = callee.child_by_field_name("arguments") {
+                for arg in args.named_children(&mut args.walk()).filter(|a| a.kind() != "comment") {
+                    arguments.push(arg.utf8_text(source.as_bytes())?.to_string());
+                }
+            }
+            callee = callee.child_by_field_name("function").unwrap_or(callee);
+        }
+
+        let expression = callee.utf8_text(source.as_bytes())?.to_string();
+        let (name, import_name) = if is_dotted_name(&callee) {
+            resolve_reference(imports, &expression)
+        } else {
+            (expression.clone(), None)
+        };
+
+        decorators.push(Decorator {
+            name,
+            expression,
+            import_name,
+            arguments,
+            line: decorator.start_position().row + 1,
+            span: Span::from_node(&decorator),
+        });
+    }
+
+    Ok(decorators)
+}
+
+
+/// Atributos de datos de una clase: asignaciones en su cuerpo y sobre
+/// `self`/`cls` en sus métodos. Cada nombre se registra una vez por tipo de
+/// atributo, en su primera asignación.
+fn parse_class_fields(source: &str, body: &Node) -> Result<Vec<FieldInfo>, Utf8Error> {
+    let mut fields = vec![];
+
+    let mut cursor = body.walk();
+    for child in body.named_children(&mut cursor) {
+        match child.kind() {
+            "expression_statement" => {
+                for assignment in child.named_children(&mut child.walk()).filter(|c| c.kind() == "assignment") {
+                    collect_assigned_fields(source, &assignment, None, None, FieldKind::Class, &mut fields)?;
+                }
+            }
+            "function_definition" => collect_method_fields(source, &child, &mut fields)?,
+            "decorated_definition" => {
+                if let Some(method) = child.child_by_field_name("definition").filter(|d| d.kind() == "function_definition") {
+                    collect_method_fields(source, &method, &mut fields)?;
+                }
             }
             _ => {}
         }
     }
 
-    ImportInfo { name: file_name, line: node.start_position().row + 1, path: import_path, imported_names: functions }
+    Ok(fields)
+}
+
+
+/// `self.x = ...` en un método, o `cls.x = ...` en un `@classmethod`. Los
+/// `@staticmethod` no tienen receptor.
+fn collect_method_fields(source: &str, method: &Node, fields: &mut Vec<FieldInfo>) -> Result<(), Utf8Error> {
+    let decorators: Vec<&str> = method.parent()
+        .filter(|p| p.kind() == "decorated_definition")
+        .map(|p| p.named_children(&mut p.walk())
+            .filter(|c| c.kind() == "decorator")
+            .filter_map(|d| d.named_child(0)?.utf8_text(source.as_bytes()).ok())
+            .collect())
+        .unwrap_or_default();
+    if decorators.contains(&"staticmethod") {
+        return Ok(());
+    }
+    let kind = if decorators.contains(&"classmethod") { FieldKind::Class } else { FieldKind::Instance };
+
+    let receiver = method.child_by_field_name("parameters")
+        .and_then(|p| p.named_child(0))
+        .and_then(|p| if p.kind() == "identifier" { Some(p) } else { p.named_children(&mut p.walk()).find(|c| c.kind() == "identifier") });
+    let (Some(receiver), Some(body)) = (receiver, method.child_by_field_name("body")) else {
+        return Ok(());
+    };
+    let receiver = receiver.utf8_text(source.as_bytes())?;
+    let method_name = method.child_by_field_name("name").map(|n| n.utf8_text(source.as_bytes())).transpose()?;
+
+    collect_receiver_fields(source, &body, receiver, method_name, kind, fields)
+}
+
+
+fn collect_receiver_fields(
+    source: &str,
+    node: &Node,
+    receiver: &str,
+    method_name: Option<&str>,
+    kind: FieldKind,
+    fields: &mut Vec<FieldInfo>,
+) -> Result<(), Utf8Error> {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            // Otro ámbito: su `self` no es el de esta clase
+            "function_definition" | "class_definition" | "lambda" => {}
+            "assignment" => collect_assigned_fields(source, &child, Some(receiver), method_name, kind, fields)?,
+            _ => collect_receiver_fields(source, &child, receiver, method_name, kind, fields)?,
+        }
+    }
+    Ok(())
 }
 
 
-fn get_function_parameters<'a>(source: &'a str, node: &tree_sitter::Node<'a>) -> Vec<ParameterInfo> {
+/// Registra los destinos de una asignación, incluidas las encadenadas
+/// (`a = b = 0`). Con `receiver` solo cuentan los `receiver.x`.
+fn collect_assigned_fields(
+    source: &str,
+    assignment: &Node,
+    receiver: Option<&str>,
+    declared_in: Option<&str>,
+    kind: FieldKind,
+    fields: &mut Vec<FieldInfo>,
+) -> Result<(), Utf8Error> {
+    let mut targets = vec![assignment.child_by_field_name("left")];
+    let mut right = assignment.child_by_field_name("right");
+    while let Some(inner) = right.filter(|r| r.kind() == "assignment") {
+        targets.push(inner.child_by_field_name("left"));
+        right = inner.child_by_field_name("right");
+    }
+
+    let annotation = assignment.child_by_field_name("type").map(|n| n.utf8_text(source.as_bytes())).transpose()?;
+    let value = right.map(|n| n.utf8_text(source.as_bytes())).transpose()?;
+
+    for target in targets.into_iter().flatten() {
+        let name_node = match receiver {
+            None => Some(target).filter(|t| t.kind() == "identifier"),
+            Some(receiver) => Some(target)
+                .filter(|t| t.kind() == "attribute")
+                .filter(|t| t.child_by_field_name("object")
+                    .is_some_and(|o| o.kind() == "identifier" && o.utf8_text(source.as_bytes()) == Ok(receiver)))
+                .and_then(|t| t.child_by_field_name("attribute")),
+        };
+        let Some(name_node) = name_node else { continue };
+        let name = name_node.utf8_text(source.as_bytes())?;
+        if fields.iter().any(|f| f.name == name && f.kind == kind) {
+            continue;
+        }
+
+        fields.push(FieldInfo {
+            name: name.to_string(),
+            annotation: annotation.map(|a| a.to_string()),
+            value: value.map(|v| v.to_string()),
+            declared_in: declared_in.map(|m| m.to_string()),
+            kind,
+            documentation: None,
+            line: assignment.start_position().row + 1,
+            span: Span::from_node(assignment),
+        });
+    }
+
+    Ok(())
+}
+
+
+fn get_function_parameters<'a>(source: &'a str, node: &tree_sitter::Node<'a>) -> Result<Vec<ParameterInfo>, Utf8Error> {
     let mut params: Vec<ParameterInfo> = Vec::new();
+    // Después de `*` o `*args` los parámetros solo se pasan por nombre
+    let mut keyword_only = false;
+
     if let Some(param_node) = node.child_by_field_name("parameters") {
         for child in param_node.named_children(&mut param_node.walk()) {
+            let kind = if keyword_only { ParameterKind::KeywordOnly } else { ParameterKind::PositionalOrKeyword };
             match child.kind() {
                 "identifier" => {
-                    let name = child.utf8_text(source.as_bytes()).unwrap().to_string();
-                    params.push(ParameterInfo {name, default_value: None, param_type: None});
+                    let name = child.utf8_text(source.as_bytes())?.to_string();
+                    params.push(ParameterInfo {name, kind, default_value: None, param_type: None, description: None, span: Span::from_node(&child)});
                 }
                 "default_parameter" => {
                     if let Some(node_name) = child.child_by_field_name("name") {
-                        if let Ok(name) = node_name.utf8_text(source.as_bytes()) {
-                            let node_default_value = child.child_by_field_name("value").unwrap();
-                            let default_value = node_default_value.utf8_text(source.as_bytes()).unwrap().to_string();
-                            params.push(ParameterInfo {name: name.to_string(), default_value: Some(default_value), param_type: None});
-                        }
+                        let name = node_name.utf8_text(source.as_bytes())?.to_string();
+                        let default_value = match child.child_by_field_name("value") {
+                            Some(n) => Some(n.utf8_text(source.as_bytes())?.to_string()),
+                            None => None,
+                        };
+                        params.push(ParameterInfo {name, kind, default_value, param_type: None, description: None, span: Span::from_node(&child)});
                     }
                 }
                 "typed_parameter" => {
                     let mut name = "<?>".to_string();
+                    let mut kind = kind;
                     let mut param_type: Option<String> = None;
 
                     let mut sub_cursor = child.walk();
@@ -191,6 +614,12 @@ fn get_function_parameters<'a>(source: &'a str, node: &tree_sitter::Node<'a>) ->
                             "identifier" => {
                                 name = sub.utf8_text(source.as_bytes()).unwrap_or("<?>").to_string();
                             }
+                            // `*args: int`, `**kwargs: str`
+                            "list_splat_pattern" | "dictionary_splat_pattern" => {
+                                name = splat_name(source, &sub)?;
+                                kind = splat_kind(&sub);
+                                keyword_only = true;
+                            }
                             "type" => {
                                 let parsed_param_type = sub.utf8_text(source.as_bytes()).unwrap_or("").trim().to_string();
                                 if !parsed_param_type.is_empty() {
@@ -200,24 +629,55 @@ fn get_function_parameters<'a>(source: &'a str, node: &tree_sitter::Node<'a>) ->
                             _ => {}
                         }
                     }
-                    params.push(ParameterInfo {name, default_value: None, param_type});
+                    params.push(ParameterInfo {name, kind, default_value: None, param_type, description: None, span: Span::from_node(&child)});
                 }
                 "typed_default_parameter" => {
                     if let Some(node_name) = child.child_by_field_name("name") {
-                        if let Ok(name) = node_name.utf8_text(source.as_bytes()) {
-                            let node_default_value = child.child_by_field_name("value").unwrap();
-                            let default_value = node_default_value.utf8_text(source.as_bytes()).unwrap().to_string();
-                            let node_param_type = child.child_by_field_name("type").unwrap();
-                            let param_type = node_param_type.utf8_text(source.as_bytes()).unwrap().to_string();
-                            params.push(ParameterInfo {name: name.to_string(), default_value: Some(default_value), param_type: Some(param_type)});
-                        }
+                        let name = node_name.utf8_text(source.as_bytes())?.to_string();
+                        let default_value = match child.child_by_field_name("value") {
+                            Some(n) => Some(n.utf8_text(source.as_bytes())?.to_string()),
+                            None => None,
+                        };
+                        let param_type = match child.child_by_field_name("type") {
+                            Some(n) => Some(n.utf8_text(source.as_bytes())?.to_string()),
+                            None => None,
+                        };
+                        params.push(ParameterInfo {name, kind, default_value, param_type, description: None, span: Span::from_node(&child)});
+                    }
+                }
+                "list_splat_pattern" | "dictionary_splat_pattern" => {
+                    let name = splat_name(source, &child)?;
+                    params.push(ParameterInfo {name, kind: splat_kind(&child), default_value: None, param_type: None, description: None, span: Span::from_node(&child)});
+                    keyword_only = true;
+                }
+                // `*` sin nombre
+                "keyword_separator" => keyword_only = true,
+                // `/`: lo anterior solo se pasa por posición
+                "positional_separator" => {
+                    for param in params.iter_mut().filter(|p| p.kind == ParameterKind::PositionalOrKeyword) {
+                        param.kind = ParameterKind::PositionalOnly;
                     }
                 }
                 _ => {}
             }
         }
     }
-    params
+    Ok(params)
+}
+
+
+/// `args` para `*args`, `kwargs` para `**kwargs`.
+fn splat_name(source: &str, node: &Node) -> Result<String, Utf8Error> {
+    let name = node.named_children(&mut node.walk())
+        .find(|c| c.kind() == "identifier")
+        .map(|n| n.utf8_text(source.as_bytes()))
+        .transpose()?
+        .unwrap_or("<?>");
+    Ok(name.to_string())
+}
+
+fn splat_kind(node: &Node) -> ParameterKind {
+    if node.kind() == "dictionary_splat_pattern" { ParameterKind::VarKeyword } else { ParameterKind::VarPositional }
 }
 
 
@@ -245,7 +705,7 @@ fn find_local_variables(source: &str, node: &tree_sitter::Node) -> Vec<LocalVari
                     .and_then(|n| n.utf8_text(source.as_bytes()).ok())
                     .map(|s| {
                         // si es obj.method() quedarnos solo con el nombre base
-                        s.split('.').last().unwrap_or(s).to_string()
+                        s.rsplit('.').next().unwrap_or(s).to_string()
                     });
 
                 if let Some(name) = var_name {
@@ -253,6 +713,7 @@ fn find_local_variables(source: &str, node: &tree_sitter::Node) -> Vec<LocalVari
                         name,
                         assigned_from,
                         line: child.start_position().row + 1,
+                        span: Span::from_node(&child),
                     });
                 }
 
@@ -270,7 +731,7 @@ fn find_local_variables(source: &str, node: &tree_sitter::Node) -> Vec<LocalVari
     variables
 }
 
-fn find_calls<'a>(source: &'a str, node: &tree_sitter::Node<'a>, imports: &[ImportInfo]) -> Vec<FunctionCall> {
+fn find_calls<'a>(source: &'a str, node: &tree_sitter::Node<'a>, imports: &[ImportInfo]) -> Result<Vec<FunctionCall>, Utf8Error> {
     let mut cursor = node.walk();
     let mut calls: Vec<FunctionCall> = vec![];
 
@@ -279,48 +740,128 @@ fn find_calls<'a>(source: &'a str, node: &tree_sitter::Node<'a>, imports: &[Impo
             // Nodo de llamada de función en Python
             "call" => {
                 if let Some(func_node) = child.child_by_field_name("function") {
-                    let name = func_node.utf8_text(source.as_bytes()).unwrap().to_string();
-                    let mut function_name = String::new();
-                    let mut import_name = None;
-                    if name.clone().contains('.') {
-                      let import_fuction_name: Vec<&str> = name.split('.').collect();
-                      function_name = import_fuction_name.get(1).unwrap().to_string();
-                      import_name = Some(import_fuction_name.get(0).unwrap().to_string());
-                    } else {
-                      function_name = name.clone();
-                      import_name = imports.iter()
-                        .find(|i| i.imported_names.contains(&name))
-                        .map(|i| i.name.clone());
+                    let line = child.start_position().row + 1;
+                    let span = Span::from_node(&child);
+                    let awaited = is_awaited(&child);
+
+                    // Llamada encadenada (ej: `client.get(url).json()`): el
+                    // objeto es el resultado de otra llamada, no un import
+                    if let Some(object) = func_node.child_by_field_name("object").filter(|o| !is_dotted_name(o)) {
+                        let function_name = func_node.child_by_field_name("attribute")
+                            .map(|n| n.utf8_text(source.as_bytes()))
+                            .transpose()?
+                            .unwrap_or_default()
+                            .to_string();
+                        let object_name = object.utf8_text(source.as_bytes())?.to_string();
+                        calls.push(FunctionCall { name: function_name, line, span, import_name: None, object_name: Some(object_name), awaited });
+                        calls.extend(find_calls(source, &child, imports)?);
+                        continue;
                     }
 
-                    if let Some(prefix) = import_name {
-                      let is_real_import = imports.iter().any(|i| {
-                          i.name == prefix || 
-                          i.name.ends_with(&format!(".{}", prefix)) ||
-                          i.imported_names.contains(&prefix)
-                      });
-
-                      if is_real_import {
-                        calls.push(FunctionCall { name: function_name, line: node.start_position().row + 1, import_name: Some(prefix), object_name: None });
-                      } else {
-                        calls.push(FunctionCall { name: function_name, line: node.start_position().row + 1, import_name: None, object_name: Some(prefix) });
-                      }
+                    let name = func_node.utf8_text(source.as_bytes())?.to_string();
+
+                    if let Some((import_name, function_name)) = resolve_import_prefix(imports, &name) {
+                        calls.push(FunctionCall { name: function_name, line, span, import_name: Some(import_name), object_name: None, awaited });
+                    } else if let Some((prefix, rest)) = name.split_once('.') {
+                        let function_name = rest.split('.').next().unwrap_or(rest).to_string();
+                        calls.push(FunctionCall { name: function_name, line, span, import_name: None, object_name: Some(prefix.to_string()), awaited });
+                    } else if let Some((import, imported)) = imports.iter().find_map(|i| i.imported(&name).map(|n| (i, n))) {
+                        // Nombre importado directamente, quizás con alias: se
+                        // registra con su nombre original en el módulo
+                        calls.push(FunctionCall { name: imported.name.clone(), line, span, import_name: Some(import.name.clone()), object_name: None, awaited });
                     } else {
-                      calls.push(FunctionCall { name: function_name, line: node.start_position().row + 1, import_name, object_name: None });
+                        calls.push(FunctionCall { name, line, span, import_name: None, object_name: None, awaited });
                     }
-
                 }
-                calls.extend(find_calls(source, &child, imports))
+                calls.extend(find_calls(source, &child, imports)?)
             }
+            // Las funciones y clases anidadas registran sus propias llamadas
+            "function_definition" | "class_definition" | "lambda" => {}
             // Recorrer recursivamente el resto del cuerpo
-            _ => calls.extend(find_calls(source, &child, imports)),
+            _ => calls.extend(find_calls(source, &child, imports)?),
         }
     }
 
-    calls
+    Ok(calls)
 }
  
 
+/// Si la llamada es el operando de un `await`, con o sin paréntesis.
+fn is_awaited(call: &tree_sitter::Node) -> bool {
+    let mut parent = call.parent();
+    while let Some(p) = parent
+        && p.kind() == "parenthesized_expression"
+    {
+        parent = p.parent();
+    }
+    parent.is_some_and(|p| p.kind() == "await")
+}
+
+
+/// Si el cuerpo tiene un `yield` propio; los de funciones, lambdas o clases
+/// anidadas no cuentan.
+fn contains_yield(node: &tree_sitter::Node) -> bool {
+    node.named_children(&mut node.walk()).any(|child| match child.kind() {
+        "yield" => true,
+        "function_definition" | "lambda" | "class_definition" => false,
+        _ => contains_yield(&child),
+    })
+}
+
+
+/// Para una llamada `prefijo.funcion(...)` cuyo prefijo es un módulo o un
+/// nombre importado, devuelve el nombre original del import (sin alias) y
+/// el de la función llamada. `import os.path` se reconoce en
+/// `os.path.join(...)` antes que `os`.
+fn resolve_import_prefix(imports: &[ImportInfo], callee: &str) -> Option<(String, String)> {
+    let after = |prefix: &str| -> Option<String> {
+        let rest = callee.strip_prefix(prefix)?.strip_prefix('.')?;
+        Some(rest.split('.').next().unwrap_or(rest).to_string())
+    };
+
+    let module = imports.iter()
+        .filter_map(|i| after(i.local_name()).map(|function| (i, function)))
+        .max_by_key(|(i, _)| i.local_name().len());
+    if let Some((import, function)) = module {
+        return Some((import.name.clone(), function));
+    }
+
+    let (prefix, _) = callee.split_once('.')?;
+    let function = after(prefix)?;
+    imports.iter().find_map(|i| {
+        if let Some(imported) = i.imported(prefix) {
+            return Some((imported.name.clone(), function.clone()));
+        }
+        // `import a.b` también deja visible `b` en algunos usos
+        (i.alias.is_none() && i.name.ends_with(&format!(".{}", prefix))).then(|| (prefix.to_string(), function.clone()))
+    })
+}
+
+
+/// Nombre original y módulo de una referencia a un nombre (`dataclass`,
+/// `attr.s`, `models.Model`), con las mismas reglas que las llamadas. Si no
+/// viene de un import queda el último segmento.
+fn resolve_reference(imports: &[ImportInfo], reference: &str) -> (String, Option<String>) {
+    if let Some((import_name, name)) = resolve_import_prefix(imports, reference) {
+        return (name, Some(import_name));
+    }
+    if let Some((import, imported)) = imports.iter().find_map(|i| i.imported(reference).map(|n| (i, n))) {
+        return (imported.name.clone(), Some(import.name.clone()));
+    }
+    (reference.rsplit('.').next().unwrap_or(reference).to_string(), None)
+}
+
+
+/// `a` o `a.b.c`, sin llamadas ni subíndices en el medio.
+fn is_dotted_name(node: &Node) -> bool {
+    match node.kind() {
+        "identifier" => true,
+        "attribute" => node.child_by_field_name("object").is_some_and(|o| is_dotted_name(&o)),
+        _ => false,
+    }
+}
+
+
 #[allow(dead_code)]
 fn print_tree(source: &str, node: Node, indent: usize) {
     let indent_str = " ".repeat(indent);
@@ -345,6 +886,11 @@ pub fn resolve_python_import(
 }
 
 
+fn python_rule(import_path: &str) -> ResolutionRule {
+    if import_path.starts_with('.') { ResolutionRule::Relative } else { ResolutionRule::ProjectRoot }
+}
+
+
 fn resolve_relative_python_import(current_file: &Path, import_path: &str) -> Option<PathBuf> {
     let mut current_dir = current_file.parent()?.to_path_buf();
 
@@ -396,16 +942,16 @@ fn resolve_absolute_python_import(
 fn find_python_module(base: &Path) -> Option<PathBuf> {
     let file = base.with_extension("py");
 
-    if file.exists() {
+    if probe::exists(&file) {
         return file.canonicalize().ok();
     }
 
     let init = base.join("__init__.py");
-    if init.exists() {
+    if probe::exists(&init) {
         return init.canonicalize().ok();
     }
 
-    if base.is_dir() {
+    if probe::is_dir(base) {
         return base.canonicalize().ok();
     }
 
@@ -0,0 +1,96 @@
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::parser::language::LanguageAnalyzer;
+use crate::parser::{javascript::JavaScriptAnalyzer, python::PythonAnalyzer, typescript::TypeScriptAnalyzer};
+
+/// Conjunto de backends disponibles, consultado por extensión de archivo.
+/// Si dos backends declaran la misma extensión gana el último registrado.
+#[derive(Clone)]
+pub struct LanguageRegistry {
+    analyzers: Vec<Arc<dyn LanguageAnalyzer>>,
+}
+
+impl LanguageRegistry {
+    pub fn empty() -> Self {
+        LanguageRegistry { analyzers: vec![] }
+    }
+
+    pub fn register(&mut self, analyzer: Arc<dyn LanguageAnalyzer>) {
+        self.analyzers.push(analyzer);
+    }
+
+    pub fn for_extension(&self, extension: &str) -> Option<Arc<dyn LanguageAnalyzer>> {
+        self.analyzers.iter()
+            .rev()
+            .find(|a| a.extensions().contains(&extension))
+            .cloned()
+    }
+
+    pub fn for_path(&self, path: &Path) -> Option<Arc<dyn LanguageAnalyzer>> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(|e| self.for_extension(e))
+    }
+
+    pub fn languages(&self) -> Vec<String> {
+        let mut names: Vec<String> = vec![];
+        for analyzer in &self.analyzers {
+            if !names.iter().any(|n| n == analyzer.name()) {
+                names.push(analyzer.name().to_string());
+            }
+        }
+        names
+    }
+
+    pub fn extensions(&self) -> Vec<String> {
+        let mut extensions: Vec<String> = vec![];
+        for analyzer in &self.analyzers {
+            for ext in analyzer.extensions() {
+                if !extensions.iter().any(|e| e == ext) {
+                    extensions.push(ext.to_string());
+                }
+            }
+        }
+        extensions
+    }
+}
+
+impl Default for LanguageRegistry {
+    fn default() -> Self {
+        let mut registry = LanguageRegistry::empty();
+        registry.register(Arc::new(PythonAnalyzer));
+        registry.register(Arc::new(TypeScriptAnalyzer));
+        registry.register(Arc::new(JavaScriptAnalyzer));
+        registry
+    }
+}
+
+
+fn global() -> &'static RwLock<LanguageRegistry> {
+    static REGISTRY: OnceLock<RwLock<LanguageRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(LanguageRegistry::default()))
+}
+
+/// Registra un backend en el registro global que usa `parse_file`.
+pub fn register_language(analyzer: Arc<dyn LanguageAnalyzer>) {
+    global().write().unwrap_or_else(|e| e.into_inner()).register(analyzer);
+}
+
+/// Backend del registro global para el archivo dado.
+pub fn analyzer_for_path(path: &Path) -> Option<Arc<dyn LanguageAnalyzer>> {
+    global().read().unwrap_or_else(|e| e.into_inner()).for_path(path)
+}
+
+/// Copia del registro global en su estado actual.
+pub fn registry() -> LanguageRegistry {
+    global().read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub fn supported_languages() -> Vec<String> {
+    registry().languages()
+}
+
+pub fn supported_extensions() -> Vec<String> {
+    registry().extensions()
+}
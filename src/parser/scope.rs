@@ -0,0 +1,37 @@
+use crate::models::{analysis_result::AnalysisResult, class_info::ClassInfo, function_info::FunctionInfo};
+
+/// Dónde se guardan las funciones y clases encontradas: en el módulo, en una
+/// clase (métodos y clases anidadas) o en una función (closures y clases
+/// locales).
+pub(crate) enum Scope<'a> {
+    Module,
+    Class(&'a mut ClassInfo),
+    Function(&'a mut FunctionInfo),
+}
+
+impl Scope<'_> {
+    /// `qualified_name` de lo que se define en este ámbito y el de su padre.
+    pub fn qualify(&self, name: &str) -> (String, Option<String>) {
+        match self {
+            Scope::Module => (name.to_string(), None),
+            Scope::Class(class) => (format!("{}.{}", class.qualified_name, name), Some(class.qualified_name.clone())),
+            Scope::Function(function) => (format!("{}.<locals>.{}", function.qualified_name, name), Some(function.qualified_name.clone())),
+        }
+    }
+
+    pub fn push_function(&mut self, result: &mut AnalysisResult, function: FunctionInfo) {
+        match self {
+            Scope::Module => result.functions.push(function),
+            Scope::Class(class) => class.methods.push(function),
+            Scope::Function(parent) => parent.functions.push(function),
+        }
+    }
+
+    pub fn push_class(&mut self, result: &mut AnalysisResult, class: ClassInfo) {
+        match self {
+            Scope::Module => result.classes.push(class),
+            Scope::Class(parent) => parent.classes.push(class),
+            Scope::Function(parent) => parent.classes.push(class),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::models::analysis_result::AnalysisResult;
+use crate::models::class_info::ClassInfo;
+use crate::models::function_info::FunctionInfo;
+
+/// Extensiones de varias partes que se quitan enteras del nombre de módulo.
+const DECLARATION_SUFFIXES: &[&str] = &[".d.ts", ".d.mts", ".d.cts"];
+
+/// Nombre de módulo de un archivo, relativo a la raíz de resolución más
+/// cercana: `pkg/sub/mod.py` → `pkg.sub.mod`, `pkg/__init__.py` → `pkg`. Un
+/// archivo fuera de las raíces usa solo su nombre.
+pub fn module_name(path: &Path, roots: &[PathBuf]) -> String {
+    let canonical = path.canonicalize().ok();
+    let relative = roots.iter()
+        .filter_map(|root| {
+            path.strip_prefix(root).ok().map(Path::to_path_buf).or_else(|| {
+                let root = root.canonicalize().ok()?;
+                canonical.as_ref()?.strip_prefix(root).ok().map(Path::to_path_buf)
+            })
+        })
+        .min_by_key(|relative| relative.components().count())
+        .unwrap_or_else(|| PathBuf::from(path.file_name().unwrap_or_default()));
+
+    let mut parts: Vec<String> = relative.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if let Some(file) = parts.pop() {
+        let stem = DECLARATION_SUFFIXES.iter()
+            .find_map(|suffix| file.strip_suffix(suffix))
+            .or_else(|| file.rsplit_once('.').map(|(stem, _)| stem))
+            .unwrap_or(&file)
+            .to_string();
+        if stem != "__init__" || parts.is_empty() {
+            parts.push(stem);
+        }
+    }
+    parts.join(".")
+}
+
+
+/// Completa `full_name` (`modulo.Clase.metodo`) e `id` de todas las
+/// funciones y clases, incluidas las anidadas. El id depende solo del tipo
+/// de símbolo y de su nombre completo, así que no cambia al editar otras
+/// partes del archivo; las redefiniciones con el mismo nombre se numeran en
+/// orden de aparición.
+pub fn assign_symbol_ids(result: &mut AnalysisResult, module: &str) {
+    let mut seen = HashMap::new();
+    assign_nested(&mut result.functions, &mut result.classes, module, &mut seen);
+}
+
+fn assign_nested(functions: &mut [FunctionInfo], classes: &mut [ClassInfo], module: &str, seen: &mut HashMap<String, usize>) {
+    for function in functions {
+        function.full_name = full_name(module, &function.qualified_name);
+        function.id = symbol_id("function", &function.full_name, seen);
+        assign_nested(&mut function.functions, &mut function.classes, module, seen);
+    }
+    for class in classes {
+        class.full_name = full_name(module, &class.qualified_name);
+        class.id = symbol_id("class", &class.full_name, seen);
+        assign_nested(&mut class.methods, &mut class.classes, module, seen);
+    }
+}
+
+fn full_name(module: &str, qualified_name: &str) -> String {
+    if module.is_empty() { qualified_name.to_string() } else { format!("{}.{}", module, qualified_name) }
+}
+
+fn symbol_id(kind: &str, full_name: &str, seen: &mut HashMap<String, usize>) -> String {
+    let key = format!("{}:{}", kind, full_name);
+    let occurrence = seen.entry(key.clone()).or_insert(0);
+    *occurrence += 1;
+    let key = if *occurrence > 1 { format!("{}#{}", key, occurrence) } else { key };
+
+    Sha256::digest(key.as_bytes())[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use serde_json::{Map, Value};
+
+use crate::parser::probe::{self, Probe};
+
+/// Límite de la cadena de `extends`, por si hay ciclos.
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// Opciones de `compilerOptions` que afectan la resolución de módulos, ya
+/// combinadas a lo largo de la cadena de `extends` y con rutas absolutas.
+#[derive(Debug, Clone, Default)]
+pub struct TsConfig {
+    pub path: PathBuf,
+    pub base_url: Option<PathBuf>,
+    pub paths: Vec<(String, Vec<String>)>,
+    pub root_dirs: Vec<PathBuf>,
+    /// Directorio del tsconfig que define `paths`; las rutas de `paths` son
+    /// relativas a él cuando no hay `baseUrl`.
+    paths_dir: Option<PathBuf>,
+}
+
+impl TsConfig {
+    /// Busca el `tsconfig.json` más cercano subiendo desde `start`.
+    pub fn find(start: &Path) -> Option<TsConfig> {
+        start.ancestors()
+            .map(|dir| dir.join("tsconfig.json"))
+            .find(|candidate| probe::is_file(candidate))
+            .and_then(|path| TsConfig::load(&path))
+    }
+
+    /// Carga `path` con su cadena de `extends`. Todos los archivos de un
+    /// proyecto suelen compartir el mismo tsconfig, así que el resultado se
+    /// guarda y solo se vuelve a leer si cambió alguno de los archivos que
+    /// se consultaron al cargarlo.
+    pub fn load(path: &Path) -> Option<TsConfig> {
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut loaded = loaded().lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(entry) = loaded.get(&key).filter(|e| e.is_current()) {
+            probe::replay(&entry.probes);
+            return entry.config.clone().map(|config| TsConfig { path: path.to_path_buf(), ..config });
+        }
+
+        let (config, probes) = probe::record(|| {
+            let mut config = TsConfig::default();
+            config.merge_file(path, 0)?;
+            config.path = path.to_path_buf();
+            Some(config)
+        });
+        probe::replay(&probes);
+        let stamps = probes.iter().map(|p| stamp(&p.path)).collect();
+        loaded.insert(key, Loaded { config: config.clone(), probes, stamps });
+        config
+    }
+
+    /// Directorio contra el que se resuelven los destinos de `paths`.
+    pub fn paths_base(&self) -> Option<&Path> {
+        self.base_url.as_deref().or(self.paths_dir.as_deref())
+    }
+
+    /// Patrón de `paths` que aplica a `module` y los destinos con el `*`
+    /// reemplazado. Un patrón exacto gana sobre los comodines; entre
+    /// comodines, el de prefijo más largo.
+    pub fn match_paths(&self, module: &str) -> Option<(&str, Vec<String>)> {
+        if let Some((pattern, targets)) = self.paths.iter().find(|(p, _)| p == module) {
+            return Some((pattern, targets.clone()));
+        }
+
+        let (pattern, targets, captured) = self.paths.iter()
+            .filter_map(|(pattern, targets)| {
+                let (prefix, suffix) = pattern.split_once('*')?;
+                let captured = module.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                Some((pattern, targets, captured, prefix.len()))
+            })
+            .max_by_key(|(_, _, _, prefix_len)| *prefix_len)
+            .map(|(pattern, targets, captured, _)| (pattern, targets, captured))?;
+
+        Some((pattern, targets.iter().map(|t| t.replacen('*', captured, 1)).collect()))
+    }
+
+    fn merge_file(&mut self, path: &Path, depth: usize) -> Option<()> {
+        if depth > MAX_EXTENDS_DEPTH {
+            return None;
+        }
+        let text = probe::read_to_string(path).ok()?;
+        let json: Value = serde_json::from_str(&strip_jsonc(&text)).ok()?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+
+        // Primero la base; las opciones de este archivo la pisan
+        let extends = match json.get("extends") {
+            Some(Value::String(e)) => vec![e.as_str()],
+            Some(Value::Array(list)) => list.iter().filter_map(|e| e.as_str()).collect(),
+            _ => vec![],
+        };
+        for parent in extends {
+            if let Some(parent_path) = resolve_extends(dir, parent) {
+                self.merge_file(&parent_path, depth + 1);
+            }
+        }
+
+        let Some(options) = json.get("compilerOptions").and_then(Value::as_object) else {
+            return Some(());
+        };
+        self.merge_options(dir, options);
+        Some(())
+    }
+
+    fn merge_options(&mut self, dir: &Path, options: &Map<String, Value>) {
+        if let Some(base_url) = options.get("baseUrl").and_then(Value::as_str) {
+            self.base_url = Some(dir.join(base_url));
+        }
+        if let Some(paths) = options.get("paths").and_then(Value::as_object) {
+            self.paths = paths.iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets.as_array().into_iter().flatten()
+                        .filter_map(|t| t.as_str().map(|s| s.to_string()))
+                        .collect();
+                    (pattern.clone(), targets)
+                })
+                .collect();
+            self.paths_dir = Some(dir.to_path_buf());
+        }
+        if let Some(root_dirs) = options.get("rootDirs").and_then(Value::as_array) {
+            self.root_dirs = root_dirs.iter()
+                .filter_map(|d| d.as_str().map(|d| dir.join(d)))
+                .collect();
+        }
+    }
+}
+
+
+/// Tsconfig ya cargado, con las consultas que hizo la carga y el estado de
+/// cada ruta consultada en ese momento.
+struct Loaded {
+    config: Option<TsConfig>,
+    probes: Vec<Probe>,
+    stamps: Vec<Option<(u64, SystemTime)>>,
+}
+
+impl Loaded {
+    fn is_current(&self) -> bool {
+        self.probes.iter().zip(&self.stamps).all(|(probe, seen)| stamp(&probe.path) == *seen)
+    }
+}
+
+/// Tsconfigs cargados en el proceso, por ruta canónica.
+fn loaded() -> &'static Mutex<HashMap<PathBuf, Loaded>> {
+    static LOADED: OnceLock<Mutex<HashMap<PathBuf, Loaded>>> = OnceLock::new();
+    LOADED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Tamaño y fecha de modificación de una ruta, para saber sin leerla si
+/// cambió; `None` si no existe.
+fn stamp(path: &Path) -> Option<(u64, SystemTime)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.len(), meta.modified().ok()?))
+}
+
+
+/// `extends` puede ser una ruta relativa (con o sin `.json`) o el nombre
+/// de un paquete en `node_modules`.
+fn resolve_extends(dir: &Path, extends: &str) -> Option<PathBuf> {
+    let with_json = |p: PathBuf| -> Option<PathBuf> {
+        if probe::is_file(&p) {
+            return Some(p);
+        }
+        let mut name = p.into_os_string();
+        name.push(".json");
+        Some(PathBuf::from(name)).filter(|p| probe::is_file(p))
+    };
+
+    if extends.starts_with('.') || Path::new(extends).is_absolute() {
+        return with_json(dir.join(extends));
+    }
+
+    dir.ancestors()
+        .map(|d| d.join("node_modules").join(extends))
+        .find_map(|candidate| {
+            if probe::is_dir(&candidate) {
+                Some(candidate.join("tsconfig.json")).filter(|p| probe::is_file(p))
+            } else {
+                with_json(candidate)
+            }
+        })
+}
+
+
+/// Los tsconfig admiten comentarios y comas finales (JSONC).
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.char_indices().peekable();
+    let mut in_string = false;
+
+    while let Some((i, c)) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next().map(|(_, n)| n)),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek().map(|(_, n)| *n)) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.next_if(|(_, n)| *n != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for (_, n) in chars.by_ref() {
+                    if previous == '*' && n == '/' {
+                        break;
+                    }
+                    previous = n;
+                }
+            }
+            // Coma final: la descartamos si lo siguiente es `}` o `]`
+            (',', _) => {
+                let next = skip_jsonc_trivia(&text[i + 1..]);
+                if !next.starts_with('}') && !next.starts_with(']') {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Lo que sigue a una coma, salteando espacios y comentarios.
+fn skip_jsonc_trivia(rest: &str) -> &str {
+    let mut rest = rest.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.split_once('\n').map(|(_, r)| r).unwrap_or("").trim_start();
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.split_once("*/").map(|(_, r)| r).unwrap_or("").trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
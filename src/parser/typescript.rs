@@ -1,62 +1,113 @@
 #![allow(dead_code)]
 
-use std::path::{Path, PathBuf};
-use tree_sitter::{Parser, TreeCursor, Node};
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::{Language, Tree, TreeCursor, Node};
 use crate::models::{
-    analysis_result::AnalysisResult, class_info::ClassInfo, function_call::FunctionCall, function_info::FunctionInfo, import_info::ImportInfo, local_variable::LocalVariable, parameter_info::ParameterInfo
+    analysis_result::AnalysisResult, class_info::{ClassInfo, FieldInfo, FieldKind}, documentation::Documentation, function_call::FunctionCall, function_info::FunctionInfo, import_info::{ImportInfo, ImportKind, ImportStyle, ImportedName, PackageInfo, ResolutionRule}, local_variable::LocalVariable, parameter_info::{ParameterInfo, ParameterKind}, span::Span
 };
+use crate::error::AnalysisError;
+use crate::parser::language::LanguageAnalyzer;
+use crate::parser::docstrings;
+use crate::parser::pool;
+use crate::parser::probe;
+use crate::parser::scope::Scope;
+use crate::parser::symbols;
+use crate::parser::node_modules;
+use crate::parser::tsconfig::TsConfig;
+
+pub struct TypeScriptAnalyzer;
+
+impl LanguageAnalyzer for TypeScriptAnalyzer {
+    fn name(&self) -> &str {
+        "typescript"
+    }
 
+    fn extensions(&self) -> &[&str] {
+        &["ts", "tsx", "mts", "cts"]
+    }
 
-pub fn parse(source: &str, path: &Path, root_path: &[PathBuf], is_jsx: bool) -> AnalysisResult {
-    let mut parser = Parser::new();
-    let language = if is_jsx {
-        tree_sitter_typescript::language_tsx()
-    } else {
-        tree_sitter_typescript::language_typescript()
-    };
-    parser.set_language(language).unwrap();
-    let tree = parser.parse(source, None).unwrap();
+    fn language(&self, extension: &str) -> Language {
+        match extension {
+            "tsx" => tree_sitter_typescript::language_tsx(),
+            _ => tree_sitter_typescript::language_typescript(),
+        }
+    }
+
+    fn analyze(&self, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        parse(source, path, root_path)
+    }
+
+    fn analyze_tree(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        analyze_tree(tree, source, path, root_path)
+    }
+
+    fn analyze_tree_filtered(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf], extract: &dyn Fn(&Node) -> bool) -> Result<AnalysisResult, AnalysisError> {
+        analyze_tree_filtered(tree, source, path, root_path, extract)
+    }
+
+    fn is_builtin(&self, name: &str) -> bool {
+        TS_GLOBALS.contains(&name)
+    }
+}
+
+const TS_GLOBALS: &[&str] = &[
+    "Array", "BigInt", "Boolean", "Date", "Error", "JSON", "Map", "Math", "Number", "Object", "Promise",
+    "Proxy", "Reflect", "RegExp", "Set", "String", "Symbol", "WeakMap", "WeakSet", "clearInterval",
+    "clearTimeout", "console", "decodeURIComponent", "document", "encodeURIComponent", "fetch",
+    "globalThis", "isFinite", "isNaN", "parseFloat", "parseInt", "process", "queueMicrotask", "require",
+    "setInterval", "setTimeout", "structuredClone", "window",
+];
+
+
+pub fn parse(source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("ts");
+    let tree = pool::parse_tree(TypeScriptAnalyzer.language(extension), source, None, path)?;
+    analyze_tree(&tree, source, path, root_path)
+}
+
+
+pub fn analyze_tree(tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+    analyze_tree_filtered(tree, source, path, root_path, &|_| true)
+}
+
+/// Ver `LanguageAnalyzer::analyze_tree_filtered`.
+pub fn analyze_tree_filtered(tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf], extract: &dyn Fn(&Node) -> bool) -> Result<AnalysisResult, AnalysisError> {
     let root_node = tree.root_node();
 
     // print_tree(source, root_node, 0);
 
     let mut result = AnalysisResult {
+        documentation: None,
         imports: vec![],
         functions: vec![],
         classes: vec![],
     };
 
-    let mut none_class: Option<&mut ClassInfo> = None;
-    analyze_node(path, root_path, source, &mut root_node.walk(), &mut result, &mut none_class);
+    // Los imports se recolectan antes que las funciones: los `import` se
+    // elevan al inicio del módulo y un `require` puede aparecer en cualquier
+    // lugar del archivo
+    let resolver = TsResolver::new(path, root_path);
+    collect_imports(source, &root_node, &resolver, &mut result.imports);
 
-    result
+    let mut cursor = root_node.walk();
+    for node in root_node.children(&mut cursor).filter(|node| extract(node)) {
+        analyze_node(source, &mut node.walk(), &mut result, &mut Scope::Module);
+    }
+    symbols::assign_symbol_ids(&mut result, &symbols::module_name(path, root_path));
+
+    Ok(result)
 }
 
 
-fn analyze_node(
-    path: &Path,
-    root_path: &[PathBuf],
-    source: &str,
-    cursor: &mut TreeCursor,
-    result: &mut AnalysisResult,
-    current_class: &mut Option<&mut ClassInfo>,
-) {
+fn analyze_node(source: &str, cursor: &mut TreeCursor, result: &mut AnalysisResult, scope: &mut Scope) {
     loop {
         let node = cursor.node();
         let kind = node.kind();
 
         match kind {
-            "import_statement" => {
-                let imports = parse_import_statement(source, &node, path, root_path);
-                result.imports.extend(imports);
-            }
-            "function_declaration" => {
+            "function_declaration" | "generator_function_declaration" => {
                 let func = parse_function(source, &node, &result.imports);
-                if let Some(class) = current_class.as_deref_mut() {
-                    class.methods.push(func);
-                } else {
-                    result.functions.push(func);
-                }
+                push_function(source, result, scope, func, node.child_by_field_name("body"));
             }
             "class_declaration" => {
                 let name = node
@@ -64,28 +115,39 @@ fn analyze_node(
                     .and_then(|n| n.utf8_text(source.as_bytes()).ok())
                     .unwrap_or("<unnamed>")
                     .to_string();
+                let (qualified_name, parent) = scope.qualify(&name);
 
                 let mut class_info = ClassInfo {
+                    qualified_name,
+                    parent,
+                    full_name: String::new(),
+                    id: String::new(),
                     name,
                     line: node.start_position().row + 1,
+                    span: Span::from_node(&node),
+                    bases: vec![],
+                    keywords: vec![],
+                    decorators: vec![],
+                    documentation: jsdoc(source, &node),
+                    fields: vec![],
                     methods: vec![],
+                    classes: vec![],
                 };
 
                 if let Some(body) = node.child_by_field_name("body") {
-                    let mut inner_cursor = body.walk();
-                    let mut class_ref = Some(&mut class_info);
-                    analyze_node(path, root_path, source, &mut inner_cursor, result, &mut class_ref);
+                    analyze_node(source, &mut body.walk(), result, &mut Scope::Class(&mut class_info));
                 }
 
-                result.classes.push(class_info);
+                scope.push_class(result, class_info);
             }
             "method_definition" => {
-                let func = parse_function(source, &node, &result.imports);
-                if let Some(class) = current_class.as_deref_mut() {
-                    class.methods.push(func);
+                if matches!(scope, Scope::Class(_)) {
+                    let func = parse_function(source, &node, &result.imports);
+                    push_function(source, result, scope, func, node.child_by_field_name("body"));
                 }
             }
-            "public_field_definition" => {
+            // `field_definition` es el nombre en la gramática de JavaScript
+            "public_field_definition" | "field_definition" => {
                 if let Some(arrow) = node.named_children(&mut node.walk())
                     .find(|c| c.kind() == "arrow_function")
                 {
@@ -95,82 +157,50 @@ fn analyze_node(
                         .unwrap_or("<unnamed>")
                         .to_string();
 
-                    let parameters = parse_parameters(source, &arrow);
-
-                    let return_type = arrow.child_by_field_name("return_type")
-                        .and_then(|n| n.named_children(&mut n.walk()).next())
-                        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                        .map(|s| s.to_string());
-
-                    let function_calls = arrow.named_children(&mut arrow.walk())
-                        .find(|c| c.kind() == "statement_block")
-                        .map(|body| find_calls(source, &body, &result.imports))
-                        .unwrap_or_else(|| find_calls(source, &arrow, &result.imports));
-
-                    let func = FunctionInfo {
-                        name,
-                        line: node.start_position().row + 1,
-                        end_line: node.end_position().row + 1,
-                        parameters,
-                        return_type,
-                        function_calls: Some(function_calls),
-                        local_variables: vec![]
-                    };
-
-                    if let Some(class) = current_class.as_deref_mut() {
-                        class.methods.push(func);
+                    if matches!(scope, Scope::Class(_)) {
+                        let func = parse_arrow(source, name, &node, &arrow, jsdoc(source, &node), &result.imports);
+                        push_function(source, result, scope, func, arrow.child_by_field_name("body"));
                     }
+                } else if let Scope::Class(class) = scope {
+                    class.fields.push(parse_field(source, &node));
                 }
             }
             "lexical_declaration" => {
                 let mut decl_cursor = node.walk();
                 for child in node.named_children(&mut decl_cursor) {
-                    if child.kind() == "variable_declarator" {
-                        let name = child.named_children(&mut child.walk())
-                            .find(|c| c.kind() == "identifier")
-                            .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                            .unwrap_or("<unnamed>")
-                            .to_string();
-
-                        if let Some(arrow) = child.named_children(&mut child.walk())
-                            .find(|c| c.kind() == "arrow_function" || c.kind() == "function_expression")
-                        {
-                            let parameters = parse_parameters(source, &arrow);
+                    if child.kind() != "variable_declarator" {
+                        continue;
+                    }
 
-                            let return_type = arrow.child_by_field_name("return_type")
-                                .and_then(|n| n.named_children(&mut n.walk()).next())
-                                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                                .map(|s| s.to_string());
+                    let function = child.child_by_field_name("value")
+                        .filter(|v| matches!(v.kind(), "arrow_function" | "function_expression" | "generator_function"));
+                    let Some(arrow) = function else {
+                        analyze_node(source, &mut child.walk(), result, scope);
+                        continue;
+                    };
 
-                            let function_calls = arrow.named_children(&mut arrow.walk())
-                                .find(|c| c.kind() == "statement_block")
-                                .map(|body| find_calls(source, &body, &result.imports))
-                                .unwrap_or_else(|| find_calls(source, &arrow, &result.imports));
+                    let name = child.named_children(&mut child.walk())
+                        .find(|c| c.kind() == "identifier")
+                        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                        .unwrap_or("<unnamed>")
+                        .to_string();
 
-                            let func = FunctionInfo {
-                                name,
-                                line: child.start_position().row + 1,
-                                end_line: child.end_position().row + 1,
-                                parameters,
-                                return_type,
-                                function_calls: Some(function_calls),
-                                local_variables: vec![]
-                            };
-
-                            if let Some(class) = current_class.as_deref_mut() {
-                                class.methods.push(func);
-                            } else {
-                                result.functions.push(func);
-                            }
-                        }
-                    }
+                    // El comentario precede a la declaración completa
+                    // (`const f = () => …`), no al declarador
+                    let func = parse_arrow(source, name, &child, &arrow, jsdoc(source, &node), &result.imports);
+                    push_function(source, result, scope, func, arrow.child_by_field_name("body"));
                 }
             }
             _ => {}
         }
 
-        if kind != "class_declaration" && kind != "method_definition" && kind != "public_field_definition" && cursor.goto_first_child() {
-            analyze_node(path, root_path, source, cursor, result, current_class);
+        // Los cuerpos de funciones y clases se recorren con su propio ámbito
+        let nested = matches!(kind,
+            "function_declaration" | "generator_function_declaration" | "class_declaration" | "method_definition"
+            | "public_field_definition" | "field_definition" | "lexical_declaration"
+        );
+        if !nested && cursor.goto_first_child() {
+            analyze_node(source, cursor, result, scope);
             cursor.goto_parent();
         }
 
@@ -181,88 +211,320 @@ fn analyze_node(
 }
 
 
-fn parse_import_statement(
+/// Califica la función según el ámbito donde se define, recorre su cuerpo
+/// en busca de funciones y clases locales y la agrega al ámbito.
+fn push_function(source: &str, result: &mut AnalysisResult, scope: &mut Scope, mut func: FunctionInfo, body: Option<Node>) {
+    let (qualified_name, parent) = scope.qualify(&func.name);
+    func.qualified_name = qualified_name;
+    func.parent = parent;
+
+    if let Some(body) = body {
+        analyze_node(source, &mut body.walk(), result, &mut Scope::Function(&mut func));
+    }
+    scope.push_function(result, func);
+}
+
+
+/// Arrow function o expresión de función asignada a un nombre
+/// (`const f = () => …`, `handler = async () => …` en una clase). `node` es
+/// la declaración que da la línea y el span.
+fn parse_arrow(
     source: &str,
+    name: String,
     node: &Node,
-    current_file: &Path,
-    project_roots: &[PathBuf],
-) -> Vec<ImportInfo> {
+    arrow: &Node,
+    documentation: Option<Documentation>,
+    imports: &[ImportInfo],
+) -> FunctionInfo {
+    let mut parameters = parse_parameters(source, arrow);
+    if let Some(doc) = &documentation {
+        docstrings::document_parameters(&mut parameters, doc);
+    }
+
+    let return_type = arrow.child_by_field_name("return_type")
+        .and_then(|n| n.named_children(&mut n.walk()).next())
+        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+        .map(|s| s.to_string());
+
+    let function_calls = arrow.named_children(&mut arrow.walk())
+        .find(|c| c.kind() == "statement_block")
+        .map(|body| find_calls(source, &body, imports))
+        .unwrap_or_else(|| find_calls(source, arrow, imports));
+
+    FunctionInfo {
+        qualified_name: name.clone(),
+        parent: None,
+        full_name: String::new(),
+        id: String::new(),
+        name,
+        line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        span: Span::from_node(node),
+        is_async: is_async(arrow),
+        is_generator: is_generator(arrow),
+        decorators: vec![],
+        documentation,
+        parameters,
+        return_type,
+        function_calls: Some(function_calls),
+        local_variables: vec![],
+        functions: vec![],
+        classes: vec![],
+    }
+}
+
+
+/// Campo de clase sin función (`static count: number = 0`, `#cache`).
+fn parse_field(source: &str, node: &Node) -> FieldInfo {
+    let text = |n: Node| n.utf8_text(source.as_bytes()).ok().map(|s| s.to_string());
+
+    // El nombre es `name` en TypeScript y `property` en JavaScript
+    let name = node.child_by_field_name("name")
+        .or_else(|| node.child_by_field_name("property"))
+        .and_then(text)
+        .unwrap_or_else(|| "<unnamed>".to_string());
+    let is_static = node.children(&mut node.walk()).any(|c| c.kind() == "static");
+
+    FieldInfo {
+        name,
+        annotation: node.child_by_field_name("type")
+            .and_then(|t| t.named_children(&mut t.walk()).next())
+            .and_then(text),
+        value: node.child_by_field_name("value").and_then(text),
+        declared_in: None,
+        kind: if is_static { FieldKind::Class } else { FieldKind::Instance },
+        documentation: jsdoc(source, node),
+        line: node.start_position().row + 1,
+        span: Span::from_node(node),
+    }
+}
+
+
+/// Comentario `/** */` inmediatamente anterior a una declaración, saltando
+/// sus decoradores. En una declaración exportada el comentario va antes del
+/// `export`.
+fn jsdoc(source: &str, node: &Node) -> Option<Documentation> {
+    let target = node.parent()
+        .filter(|p| p.kind() == "export_statement")
+        .unwrap_or(*node);
+
+    let mut previous = target.prev_sibling();
+    while let Some(sibling) = previous
+        && sibling.kind() == "decorator"
+    {
+        previous = sibling.prev_sibling();
+    }
+
+    let comment = previous.filter(|p| p.kind() == "comment")?;
+    docstrings::parse_jsdoc(comment.utf8_text(source.as_bytes()).ok()?)
+}
+
+
+/// Función asignada en un `const`/`let`, que tiene su propio `FunctionInfo`.
+fn is_local_function(node: &Node) -> bool {
+    node.parent()
+        .filter(|p| p.kind() == "variable_declarator")
+        .and_then(|p| p.parent())
+        .is_some_and(|p| p.kind() == "lexical_declaration")
+}
+
+/// `async function`, `async () => …`, `async m() {}`.
+fn is_async(node: &Node) -> bool {
+    node.children(&mut node.walk()).any(|c| c.kind() == "async")
+}
+
+/// `function*` (declaración o expresión) o un método `*m() {}`.
+fn is_generator(node: &Node) -> bool {
+    node.kind().starts_with("generator_function") || node.children(&mut node.walk()).any(|c| c.kind() == "*")
+}
+
+/// Si la llamada es el operando de un `await`, con o sin paréntesis.
+fn is_awaited(call: &Node) -> bool {
+    let mut parent = call.parent();
+    while let Some(p) = parent
+        && p.kind() == "parenthesized_expression"
+    {
+        parent = p.parent();
+    }
+    parent.is_some_and(|p| p.kind() == "await_expression")
+}
+
+
+fn parse_import_statement(source: &str, node: &Node, resolver: &TsResolver) -> Vec<ImportInfo> {
     let mut results = vec![];
 
     let module_name = node.children(&mut node.walk())
         .find(|c| c.kind() == "string")
-        .and_then(|n| n.child_by_field_name("fragment")
-            .or_else(|| n.named_children(&mut n.walk()).find(|c| c.kind() == "string_fragment")))
-        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-        .unwrap_or("")
-        .to_string();
-
-    let import_path = resolve_ts_import(current_file, &module_name, project_roots);
+        .and_then(|n| string_literal(source, &n))
+        .unwrap_or_default();
 
     let Some(clause) = node.children(&mut node.walk()).find(|c| c.kind() == "import_clause") else {
-        results.push(ImportInfo { name: module_name, line: node.start_position().row + 1, path: import_path, imported_names: vec![] });
+        results.push(resolver.import(&module_name, ImportStyle::Static, node, vec![]));
         return results;
     };
 
+    // `import X, { a as b }, * as ns from "m"`: cada forma del clause
+    // aporta bindings locales al mismo import
+    let mut imported_names = vec![];
     let mut cursor = clause.walk();
     for child in clause.named_children(&mut cursor) {
         match child.kind() {
+            "identifier" => {
+                if let Ok(local) = child.utf8_text(source.as_bytes()) {
+                    imported_names.push(ImportedName::default_export(local));
+                }
+            }
             "named_imports" => {
-                let mut imported_names = vec![];
                 let mut inner = child.walk();
                 for specifier in child.named_children(&mut inner) {
-                    if specifier.kind() == "import_specifier" {
-                        if let Some(id) = specifier.named_children(&mut specifier.walk())
-                            .find(|c| c.kind() == "identifier")
-                        {
-                            if let Ok(name) = id.utf8_text(source.as_bytes()) {
-                                imported_names.push(name.to_string());
-                            }
-                        }
+                    if specifier.kind() != "import_specifier" {
+                        continue;
+                    }
+                    let text = |field| specifier.child_by_field_name(field)
+                        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                        .map(|s| s.to_string());
+                    if let Some(name) = text("name") {
+                        imported_names.push(ImportedName { name, alias: text("alias"), kind: ImportKind::Named });
                     }
                 }
-                let parsed_name = module_name
-                    .trim_start_matches("./")
-                    .trim_start_matches("../")
-                    .split('/')
-                    .last()
-                    .unwrap_or(&module_name)
-                    .to_string();
-                results.push(ImportInfo {
-                    name: parsed_name,
-                    line: node.start_position().row + 1, 
-                    path: import_path.clone(),
-                    imported_names,
-                });
             }
             "namespace_import" => {
-                let alias = child.named_children(&mut child.walk())
+                if let Some(local) = child.named_children(&mut child.walk())
                     .find(|c| c.kind() == "identifier")
                     .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                    .unwrap_or("")
-                    .to_string();
-                results.push(ImportInfo {
-                    name: alias,
-                    line: node.start_position().row + 1, 
-                    path: import_path.clone(),
-                    imported_names: vec![],
-                });
+                {
+                    imported_names.push(ImportedName::namespace(local));
+                }
             }
             _ => {}
         }
     }
 
+    results.push(resolver.import(&module_name, ImportStyle::Static, node, imported_names));
+
     results
 }
 
 
+/// Recorre todo el árbol buscando imports estáticos, `require(...)` e
+/// `import(...)`.
+fn collect_imports(source: &str, node: &Node, resolver: &TsResolver, imports: &mut Vec<ImportInfo>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "import_statement" => {
+                imports.extend(parse_import_statement(source, &child, resolver));
+                continue;
+            }
+            // `const x = require("./x")`, `const { a } = await import("pkg")`
+            "variable_declarator" => {
+                let call = child.child_by_field_name("value").and_then(|v| module_call(source, &v));
+                if let Some((module, style, call_node)) = call {
+                    let imported_names = child.child_by_field_name("name")
+                        .map(|pattern| binding_names(source, &pattern))
+                        .unwrap_or_default();
+                    imports.push(resolver.import(&module, style, &child, imported_names));
+                    collect_imports(source, &call_node, resolver, imports);
+                    continue;
+                }
+            }
+            // `require("./polyfill")` o `import("./lazy").then(...)` sin binding
+            "call_expression" => {
+                if let Some((module, style, _)) = module_call(source, &child) {
+                    imports.push(resolver.import(&module, style, &child, vec![]));
+                }
+            }
+            _ => {}
+        }
+        collect_imports(source, &child, resolver, imports);
+    }
+}
+
+
+/// `require("m")` o `import("m")`, opcionalmente precedido de `await`.
+/// Devuelve el módulo, la forma del import y el nodo de la llamada.
+fn module_call<'a>(source: &str, node: &Node<'a>) -> Option<(String, ImportStyle, Node<'a>)> {
+    let call = if node.kind() == "await_expression" { node.named_child(0)? } else { *node };
+    if call.kind() != "call_expression" {
+        return None;
+    }
+
+    let function = call.child_by_field_name("function")?;
+    let style = match function.kind() {
+        "import" => ImportStyle::Dynamic,
+        "identifier" if function.utf8_text(source.as_bytes()).ok()? == "require" => ImportStyle::Require,
+        _ => return None,
+    };
+
+    let argument = call.child_by_field_name("arguments")?.named_child(0)?;
+    if argument.kind() != "string" {
+        return None;
+    }
+    Some((string_literal(source, &argument)?, style, call))
+}
+
+
+/// Bindings de `const x = ...` (el módulo completo) o de
+/// `const { a, b: c, default: d } = ...`.
+fn binding_names(source: &str, pattern: &Node) -> Vec<ImportedName> {
+    let text = |n: Node| n.utf8_text(source.as_bytes()).ok().map(|s| s.to_string());
+
+    match pattern.kind() {
+        "identifier" => text(*pattern).map(ImportedName::namespace).into_iter().collect(),
+        "object_pattern" => {
+            let mut names = vec![];
+            for property in pattern.named_children(&mut pattern.walk()) {
+                let (name, alias) = match property.kind() {
+                    "shorthand_property_identifier_pattern" => (text(property), None),
+                    "pair_pattern" => (
+                        property.child_by_field_name("key").and_then(text),
+                        property.child_by_field_name("value").filter(|v| v.kind() == "identifier").and_then(text),
+                    ),
+                    _ => continue,
+                };
+                let Some(name) = name else { continue };
+                let kind = if name == "default" { ImportKind::Default } else { ImportKind::Named };
+                names.push(ImportedName { name, alias, kind });
+            }
+            names
+        }
+        _ => vec![],
+    }
+}
+
+
+fn string_literal(source: &str, node: &Node) -> Option<String> {
+    node.child_by_field_name("fragment")
+        .or_else(|| node.named_children(&mut node.walk()).find(|c| c.kind() == "string_fragment"))
+        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+        .map(|s| s.to_string())
+}
+
+
+/// Nombre con el que se registra un módulo importado con bindings: el
+/// último segmento de la ruta (`./utils/math` → `math`).
+fn module_binding_name(module: &str) -> String {
+    module
+        .trim_start_matches("./")
+        .trim_start_matches("../")
+        .rsplit('/')
+        .next()
+        .unwrap_or(module)
+        .to_string()
+}
+
+
 fn parse_function(source: &str, node: &Node, imports: &[ImportInfo]) -> FunctionInfo {
     let name = node.child_by_field_name("name")
         .and_then(|n| n.utf8_text(source.as_bytes()).ok())
         .unwrap_or("<unnamed>")
         .to_string();
 
-    let parameters = parse_parameters(source, node);
+    let documentation = jsdoc(source, node);
+    let mut parameters = parse_parameters(source, node);
+    if let Some(doc) = &documentation {
+        docstrings::document_parameters(&mut parameters, doc);
+    }
 
     let return_type = node.child_by_field_name("return_type")
         .and_then(|n| n.named_children(&mut n.walk()).next())
@@ -276,7 +538,7 @@ fn parse_function(source: &str, node: &Node, imports: &[ImportInfo]) -> Function
         .map(|body| find_local_variables(source, &body))
         .unwrap_or_default();
 
-    FunctionInfo { name, line: node.start_position().row + 1, end_line: node.end_position().row + 1, parameters, return_type, function_calls, local_variables }
+    FunctionInfo { qualified_name: name.clone(), parent: None, full_name: String::new(), id: String::new(), name, line: node.start_position().row + 1, end_line: node.end_position().row + 1, span: Span::from_node(node), is_async: is_async(node), is_generator: is_generator(node), decorators: vec![], documentation, parameters, return_type, function_calls, local_variables, functions: vec![], classes: vec![] }
 }
 
 
@@ -305,7 +567,26 @@ fn parse_parameters(source: &str, node: &Node) -> Vec<ParameterInfo> {
                     .and_then(|n| n.utf8_text(source.as_bytes()).ok())
                     .map(|s| s.to_string());
 
-                params.push(ParameterInfo { name, param_type, default_value });
+                // `...rest: T[]` llega como `required_parameter` con un `rest_pattern`
+                let kind = match child.child_by_field_name("pattern") {
+                    Some(pattern) if pattern.kind() == "rest_pattern" => ParameterKind::VarPositional,
+                    _ => ParameterKind::PositionalOrKeyword,
+                };
+
+                params.push(ParameterInfo { name, kind, param_type, default_value, description: None, span: Span::from_node(&child) });
+            }
+            // En JavaScript los parámetros no se envuelven en `required_parameter`
+            "identifier" | "object_pattern" | "array_pattern" | "rest_pattern" => {
+                let name = child.utf8_text(source.as_bytes()).unwrap_or("<?>").to_string();
+                let kind = if child.kind() == "rest_pattern" { ParameterKind::VarPositional } else { ParameterKind::PositionalOrKeyword };
+                params.push(ParameterInfo { name, kind, param_type: None, default_value: None, description: None, span: Span::from_node(&child) });
+            }
+            "assignment_pattern" => {
+                let text = |field| child.child_by_field_name(field)
+                    .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                    .map(|s| s.to_string());
+                let name = text("left").unwrap_or_else(|| "<?>".to_string());
+                params.push(ParameterInfo { name, kind: ParameterKind::PositionalOrKeyword, param_type: None, default_value: text("right"), description: None, span: Span::from_node(&child) });
             }
             _ => {}
         }
@@ -323,6 +604,9 @@ fn find_calls(source: &str, node: &Node, imports: &[ImportInfo]) -> Vec<Function
         match child.kind() {
             "call_expression" => {
                 if let Some(func_node) = child.child_by_field_name("function") {
+                    let line = child.start_position().row + 1;
+                    let span = Span::from_node(&child);
+                    let awaited = is_awaited(&child);
                     match func_node.kind() {
                         "member_expression" => {
                             let object = func_node.child_by_field_name("object")
@@ -335,38 +619,45 @@ fn find_calls(source: &str, node: &Node, imports: &[ImportInfo]) -> Vec<Function
                                 .to_string();
 
                             // Verificar si object es un import real o una variable local
-                            let is_real_import = imports.iter().any(|i| {
-                                i.name == object || i.imported_names.contains(&object)
-                            });
-
-                            if is_real_import {
-                                calls.push(FunctionCall { 
-                                    name: property, 
-                                    line: node.start_position().row + 1, 
-                                    import_name: Some(object), 
-                                    object_name: None 
+                            let import_name = imports.iter()
+                                .find_map(|i| match i.imported(&object) {
+                                    // `ns.f()` / `React.f()`: miembro del módulo
+                                    Some(n) if n.kind != ImportKind::Named => Some(i.name.clone()),
+                                    // `{ utils }` → `utils.f()`: el nombre exportado
+                                    Some(n) => Some(n.name.clone()),
+                                    None => (i.name == object).then(|| object.clone()),
                                 });
+
+                            if import_name.is_some() {
+                                calls.push(FunctionCall { name: property, line, span, import_name, object_name: None, awaited });
                             } else {
-                                calls.push(FunctionCall { 
-                                    name: property, 
-                                    line: node.start_position().row + 1, 
-                                    import_name: None, 
-                                    object_name: Some(object)
-                                });
+                                calls.push(FunctionCall { name: property, line, span, import_name: None, object_name: Some(object), awaited });
                             }
                         }
                         "identifier" => {
-                            let name = func_node.utf8_text(source.as_bytes()).unwrap_or("").to_string();
-                            let import_name = imports.iter()
-                                .find(|i| i.imported_names.contains(&name))
-                                .map(|i| i.name.clone());
-                            calls.push(FunctionCall { name, line: node.start_position().row + 1, import_name, object_name: None });
+                            let local = func_node.utf8_text(source.as_bytes()).unwrap_or("").to_string();
+                            let imported = imports.iter().find_map(|i| i.imported(&local).map(|n| (i, n)));
+
+                            match imported {
+                                // `{ a as b }` → `b()` se registra como `a`
+                                Some((import, n)) if n.kind == ImportKind::Named => {
+                                    calls.push(FunctionCall { name: n.name.clone(), line, span, import_name: Some(import.name.clone()), object_name: None, awaited });
+                                }
+                                Some((import, _)) => {
+                                    calls.push(FunctionCall { name: local, line, span, import_name: Some(import.name.clone()), object_name: None, awaited });
+                                }
+                                None => calls.push(FunctionCall { name: local, line, span, import_name: None, object_name: None, awaited }),
+                            }
                         }
                         _ => {}
                     }
                 }
                 calls.extend(find_calls(source, &child, imports));
             }
+            // Las funciones y clases locales registran sus propias llamadas;
+            // los callbacks anónimos (`items.map(x => f(x))`) no
+            "function_declaration" | "generator_function_declaration" | "class_declaration" => {}
+            "arrow_function" | "function_expression" | "generator_function" if is_local_function(&child) => {}
             _ => calls.extend(find_calls(source, &child, imports)),
         }
     }
@@ -416,6 +707,7 @@ fn find_local_variables(source: &str, node: &Node) -> Vec<LocalVariable> {
                                 name,
                                 assigned_from,
                                 line: declarator.start_position().row + 1,
+                                span: Span::from_node(&declarator),
                             });
                         }
                     }
@@ -433,38 +725,161 @@ fn find_local_variables(source: &str, node: &Node) -> Vec<LocalVariable> {
     variables
 }
 
-fn resolve_ts_import(current_file: &Path, module: &str, project_roots: &[PathBuf]) -> Option<PathBuf> {
-    if module.starts_with('.') {
-        let dir = current_file.parent()?;
-        let base = dir.join(module);
-        return find_ts_module(&base);
+/// Resolución de módulos para un archivo, según el `tsconfig.json` más
+/// cercano, las raíces del proyecto y los `node_modules`.
+pub struct TsResolver<'a> {
+    current_file: &'a Path,
+    project_roots: &'a [PathBuf],
+    config: Option<TsConfig>,
+}
+
+/// Archivo encontrado para un import y cómo se llegó a él.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    pub path: PathBuf,
+    pub rule: ResolutionRule,
+    pub package: Option<PackageInfo>,
+}
+
+impl<'a> TsResolver<'a> {
+    pub fn new(current_file: &'a Path, project_roots: &'a [PathBuf]) -> Self {
+        let config = current_file.parent().and_then(TsConfig::find);
+        TsResolver { current_file, project_roots, config }
+    }
+
+    pub fn resolve(&self, module: &str) -> Option<Resolution> {
+        let local = |path: PathBuf, rule: ResolutionRule| Resolution { path, rule, package: None };
+
+        if module.starts_with('.') {
+            return self.resolve_relative(module).map(|(path, rule)| local(path, rule));
+        }
+
+        if let Some(config) = &self.config {
+            if let Some((pattern, targets)) = config.match_paths(module)
+                && let Some(base) = config.paths_base()
+            {
+                for target in targets {
+                    if let Some(found) = find_ts_module(&base.join(target)) {
+                        return Some(local(found, ResolutionRule::Paths { pattern: pattern.to_string() }));
+                    }
+                }
+            }
+            if let Some(base_url) = &config.base_url
+                && let Some(found) = find_ts_module(&base_url.join(module))
+            {
+                return Some(local(found, ResolutionRule::BaseUrl));
+            }
+        }
+
+        if let Some(found) = self.project_roots.iter().find_map(|root| find_ts_module(&root.join(module))) {
+            return Some(local(found, ResolutionRule::ProjectRoot));
+        }
+
+        let dir = self.current_file.parent()?;
+        node_modules::resolve_package(dir, module, find_ts_module)
+            .map(|(path, package)| Resolution { path, rule: ResolutionRule::NodeModules, package: Some(package) })
     }
 
-    for root in project_roots {
-        let base = root.join(module);
+    /// `ImportInfo` para `module`, declarado en `node`. Sin bindings se
+    /// registra con el especificador completo (ej: `import "./polyfills"`).
+    fn import(&self, module: &str, style: ImportStyle, node: &Node, imported_names: Vec<ImportedName>) -> ImportInfo {
+        let resolution = self.resolve(module);
+        let name = if imported_names.is_empty() { module.to_string() } else { module_binding_name(module) };
+
+        ImportInfo {
+            name,
+            specifier: module.to_string(),
+            alias: None,
+            style,
+            line: node.start_position().row + 1,
+            span: Span::from_node(node),
+            path: resolution.as_ref().map(|r| r.path.clone()),
+            resolved_by: resolution.as_ref().map(|r| r.rule.clone()),
+            package: resolution.and_then(|r| r.package),
+            imported_names,
+        }
+    }
+
+    /// Un import relativo que no existe junto al archivo puede existir en
+    /// otro de los `rootDirs`, que se tratan como un único directorio.
+    fn resolve_relative(&self, module: &str) -> Option<(PathBuf, ResolutionRule)> {
+        let dir = self.current_file.parent()?;
+        let base = dir.join(module);
         if let Some(found) = find_ts_module(&base) {
-            return Some(found);
+            return Some((found, ResolutionRule::Relative));
         }
+
+        let root_dirs: Vec<PathBuf> = self.config.as_ref()?.root_dirs.iter().map(|d| normalize(d)).collect();
+        let base = normalize(&base);
+        let relative = root_dirs.iter().find_map(|d| base.strip_prefix(d).ok())?;
+        root_dirs.iter()
+            .find_map(|d| find_ts_module(&d.join(relative)))
+            .map(|found| (found, ResolutionRule::RootDirs))
     }
+}
+
 
-    None
+/// Archivo al que apunta `module` importado desde `current_file`, con las
+/// mismas reglas que el análisis (ver `TsResolver`).
+pub fn resolve_ts_import(current_file: &Path, module: &str, project_roots: &[PathBuf]) -> Option<PathBuf> {
+    TsResolver::new(current_file, project_roots).resolve(module).map(|r| r.path)
 }
 
 
-fn find_ts_module(base: &Path) -> Option<PathBuf> {
-    for ext in &["ts", "tsx", "js", "jsx"] {
-        let candidate = base.with_extension(ext);
-        if candidate.exists() {
-            return candidate.canonicalize().ok();
+/// Resuelve `.` y `..` sin tocar el sistema de archivos.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => { normalized.pop(); }
+            other => normalized.push(other),
         }
     }
+    normalized
+}
+
+
+/// Extensiones que se prueban, en orden, para un especificador sin
+/// extensión y para el `index` de un directorio.
+const TS_EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "js", "jsx"];
 
-    let index = base.join("index.ts");
-    if index.exists() {
-        return index.canonicalize().ok();
+/// Con `moduleResolution` node16/bundler los imports ESM llevan la extensión
+/// del archivo compilado: `./a.js` se refiere a `a.ts` si existe.
+const TS_EXTENSION_SUBSTITUTES: &[(&str, &[&str])] = &[
+    ("js", &["ts", "tsx", "d.ts", "js", "jsx"]),
+    ("jsx", &["tsx", "d.ts", "jsx"]),
+    ("mjs", &["mts", "d.mts", "mjs"]),
+    ("cjs", &["cts", "d.cts", "cjs"]),
+    ("ts", &["ts"]),
+    ("tsx", &["tsx"]),
+    ("mts", &["mts"]),
+    ("cts", &["cts"]),
+];
+
+fn find_ts_module(base: &Path) -> Option<PathBuf> {
+    // Las extensiones se agregan, no se reemplazan: `./user.service` es
+    // `user.service.ts`
+    let with_extension = |path: &Path, extension: &str| -> Option<PathBuf> {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".");
+        name.push(extension);
+        let candidate = PathBuf::from(name);
+        if probe::is_file(&candidate) { candidate.canonicalize().ok() } else { None }
+    };
+
+    if let Some(extension) = base.extension().and_then(|e| e.to_str())
+        && let Some((_, substitutes)) = TS_EXTENSION_SUBSTITUTES.iter().find(|(e, _)| *e == extension)
+    {
+        let stem = base.with_extension("");
+        if let Some(found) = substitutes.iter().find_map(|s| with_extension(&stem, s)) {
+            return Some(found);
+        }
     }
 
-    None
+    let index = base.join("index");
+    TS_EXTENSIONS.iter().find_map(|e| with_extension(base, e))
+        .or_else(|| TS_EXTENSIONS.iter().find_map(|e| with_extension(&index, e)))
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+
+use crate::analyzer::{self, Analyzer};
+use crate::cache::AnalysisCache;
+use crate::error::AnalysisError;
+use crate::models::{analysis_result::AnalysisResult, project_analysis::ProjectAnalysis};
+use crate::parser::probe;
+
+/// Filtros para el recorrido de un proyecto. Los globs se evalúan sobre la
+/// ruta relativa a la raíz (ej: `src/**/*.py`).
+#[derive(Debug, Clone)]
+pub struct ProjectOptions {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    respect_gitignore: bool,
+    include_hidden: bool,
+    workers: usize,
+    cache: Option<AnalysisCache>,
+}
+
+impl Default for ProjectOptions {
+    fn default() -> Self {
+        let workers = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        ProjectOptions { include: vec![], exclude: vec![], respect_gitignore: true, include_hidden: false, workers, cache: None }
+    }
+}
+
+impl ProjectOptions {
+    pub fn new() -> Self {
+        ProjectOptions::default()
+    }
+
+    /// Si hay al menos un include, solo se analizan los archivos que coinciden.
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    pub fn respect_gitignore(mut self, value: bool) -> Self {
+        self.respect_gitignore = value;
+        self
+    }
+
+    pub fn include_hidden(mut self, value: bool) -> Self {
+        self.include_hidden = value;
+        self
+    }
+
+    /// Cantidad de hilos de análisis. Por defecto, los núcleos disponibles.
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers
+    }
+
+    /// Reutiliza los resultados guardados en `cache` para los archivos cuyo
+    /// contenido y entorno de resolución no cambiaron.
+    pub fn cache(mut self, cache: AnalysisCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+}
+
+
+/// Archivos soportados bajo `root` que pasan los filtros, como rutas
+/// relativas a `root` y en orden lexicográfico.
+pub fn collect_files(analyzer: &Analyzer, root: &Path, options: &ProjectOptions) -> Result<(Vec<PathBuf>, BTreeMap<PathBuf, String>), AnalysisError> {
+    let include = build_glob_set(&options.include)?;
+    let exclude = build_glob_set(&options.exclude)?;
+
+    let walker = WalkBuilder::new(root)
+        .hidden(!options.include_hidden)
+        .git_ignore(options.respect_gitignore)
+        .git_exclude(options.respect_gitignore)
+        .git_global(options.respect_gitignore)
+        .ignore(options.respect_gitignore)
+        .parents(options.respect_gitignore)
+        .require_git(false)
+        .build();
+
+    let mut files = vec![];
+    let mut errors = BTreeMap::new();
+    for entry in walker {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                let path = match &e {
+                    ignore::Error::WithPath { path, .. } => path.strip_prefix(root).unwrap_or(path).to_path_buf(),
+                    _ => PathBuf::new(),
+                };
+                errors.insert(path, e.to_string());
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path()).to_path_buf();
+        if analyzer.registry().for_path(&relative).is_none() {
+            continue;
+        }
+        if !options.include.is_empty() && !include.is_match(&relative) {
+            continue;
+        }
+        if exclude.is_match(&relative) {
+            continue;
+        }
+        files.push(relative);
+    }
+
+    files.sort();
+    Ok((files, errors))
+}
+
+
+pub fn analyze_project(analyzer: &Analyzer, root: &Path, options: &ProjectOptions) -> Result<ProjectAnalysis, AnalysisError> {
+    if !root.is_dir() {
+        return Err(AnalysisError::Io {
+            path: root.to_path_buf(),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "project root is not a directory"),
+        });
+    }
+
+    let (files, mut errors) = collect_files(analyzer, root, options)?;
+
+    let analyze = |relative: &Path| {
+        let path = root.join(relative);
+        match &options.cache {
+            Some(cache) => analyze_cached(analyzer, cache, &path),
+            None => analyzer.analyze_file(&path),
+        }
+    };
+
+    let mut results = BTreeMap::new();
+    for (relative, outcome) in analyze_files(files, options.workers, analyze) {
+        match outcome {
+            Ok(result) => { results.insert(relative, result); }
+            Err(e) => { errors.insert(relative, e.to_string()); }
+        }
+    }
+
+    Ok(ProjectAnalysis { root: root.to_path_buf(), files: results, errors })
+}
+
+
+fn analyze_cached(analyzer: &Analyzer, cache: &AnalysisCache, path: &Path) -> Result<AnalysisResult, AnalysisError> {
+    let backend = analyzer.registry().for_path(path)
+        .ok_or_else(|| AnalysisError::UnsupportedLanguage(path.to_path_buf()))?;
+    let source = analyzer::read_source(path)?;
+
+    let key = cache.key(&source, path, backend.name(), analyzer.roots());
+    if let Some(result) = cache.get(&key) {
+        return Ok(result);
+    }
+
+    // Las rutas que consulta la resolución de imports deciden cuándo deja
+    // de valer la entrada
+    let (result, dependencies) = probe::record(|| analyzer.analyze_source(&source, path));
+    let result = result?;
+    // Un caché que no se puede escribir no invalida el análisis
+    let _ = cache.put(&key, &result, &dependencies);
+    Ok(result)
+}
+
+
+/// Reparte los archivos entre `workers` hilos. Cada hilo reutiliza sus
+/// parsers (ver `parser::pool`); el resultado conserva el orden de `files`
+/// sin importar qué hilo terminó primero.
+fn analyze_files<F>(files: Vec<PathBuf>, workers: usize, analyze: F) -> Vec<(PathBuf, Result<AnalysisResult, AnalysisError>)>
+where
+    F: Fn(&Path) -> Result<AnalysisResult, AnalysisError> + Sync,
+{
+    let workers = workers.clamp(1, files.len().max(1));
+    if workers == 1 {
+        return files.into_iter()
+            .map(|relative| {
+                let outcome = analyze(&relative);
+                (relative, outcome)
+            })
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut outcomes: Vec<(usize, Result<AnalysisResult, AnalysisError>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| scope.spawn(|| {
+                let mut local = vec![];
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(relative) = files.get(index) else { break };
+                    local.push((index, analyze(relative)));
+                }
+                local
+            }))
+            .collect();
+
+        handles.into_iter()
+            .flat_map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter()
+        .map(|(index, outcome)| (files[index].clone(), outcome))
+        .collect()
+}
+
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, AnalysisError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| AnalysisError::InvalidPattern { pattern: pattern.clone(), message: e.to_string() })?;
+        builder.add(glob);
+    }
+    builder.build()
+        .map_err(|e| AnalysisError::InvalidPattern { pattern: patterns.join(", "), message: e.to_string() })
+}
@@ -0,0 +1,515 @@
+use std::collections::{BTreeMap, HashSet};
+use std::ops::{Range, RangeInclusive};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tree_sitter::{InputEdit, Node, Point, Tree};
+
+use crate::analyzer::Analyzer;
+use crate::error::AnalysisError;
+use crate::models::analysis_result::AnalysisResult;
+use crate::models::class_info::ClassInfo;
+use crate::models::decorator::Decorator;
+use crate::models::documentation::Documentation;
+use crate::models::function_info::FunctionInfo;
+use crate::models::import_info::ImportInfo;
+use crate::models::span::Span;
+use crate::models::symbol_changes::SymbolChanges;
+use crate::parser::language::LanguageAnalyzer;
+use crate::parser::pool;
+use crate::parser::symbols;
+
+/// Reemplazo de `source[start_byte..old_end_byte]` por `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start_byte: usize,
+    pub old_end_byte: usize,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    pub fn new(start_byte: usize, old_end_byte: usize, new_text: impl Into<String>) -> Self {
+        TextEdit { start_byte, old_end_byte, new_text: new_text.into() }
+    }
+
+    pub fn insert(at: usize, text: impl Into<String>) -> Self {
+        TextEdit::new(at, at, text)
+    }
+
+    pub fn delete(start_byte: usize, end_byte: usize) -> Self {
+        TextEdit::new(start_byte, end_byte, "")
+    }
+}
+
+
+/// Análisis de un archivo abierto en un editor. Conserva el árbol anterior
+/// para que cada edición se reparsee de forma incremental con `Tree::edit`.
+pub struct AnalysisSession {
+    analyzer: Arc<dyn LanguageAnalyzer>,
+    extension: String,
+    path: PathBuf,
+    roots: Vec<PathBuf>,
+    source: String,
+    tree: Tree,
+    result: AnalysisResult,
+}
+
+impl AnalysisSession {
+    pub fn new(analyzer: &Analyzer, path: &Path, source: impl Into<String>) -> Result<Self, AnalysisError> {
+        let backend = analyzer.registry().for_path(path)
+            .ok_or_else(|| AnalysisError::UnsupportedLanguage(path.to_path_buf()))?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_string();
+        let source = source.into();
+
+        let tree = pool::parse_tree(backend.language(&extension), &source, None, path)?;
+        let result = backend.analyze_tree(&tree, &source, path, analyzer.roots())?;
+
+        Ok(AnalysisSession {
+            analyzer: backend,
+            extension,
+            path: path.to_path_buf(),
+            roots: analyzer.roots().to_vec(),
+            source,
+            tree,
+            result,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    pub fn result(&self) -> &AnalysisResult {
+        &self.result
+    }
+
+    /// Aplica una edición y devuelve los símbolos agregados, eliminados o
+    /// modificados respecto del análisis anterior.
+    pub fn apply_edit(&mut self, edit: &TextEdit) -> Result<SymbolChanges, AnalysisError> {
+        self.apply_edits(std::slice::from_ref(edit))
+    }
+
+    /// Aplica varias ediciones en orden (cada una con offsets sobre el texto
+    /// resultante de la anterior) y reparsea una sola vez. Solo se vuelven a
+    /// extraer las definiciones de nivel superior que tocan las filas
+    /// cambiadas; el resto se conserva del análisis anterior con sus
+    /// posiciones corridas.
+    pub fn apply_edits(&mut self, edits: &[TextEdit]) -> Result<SymbolChanges, AnalysisError> {
+        let mut source = self.source.clone();
+        let mut tree = self.tree.clone();
+        let mut edited_rows: Vec<RangeInclusive<usize>> = vec![];
+        let mut retained = Retained {
+            functions: self.result.functions.clone(),
+            classes: self.result.classes.clone(),
+            edited: vec![],
+        };
+
+        for edit in edits {
+            validate(&source, edit)?;
+
+            let start_position = point_at(&source, edit.start_byte);
+            let old_end_position = point_at(&source, edit.old_end_byte);
+            source.replace_range(edit.start_byte..edit.old_end_byte, &edit.new_text);
+            let new_end_byte = edit.start_byte + edit.new_text.len();
+            let new_end_position = point_at(&source, new_end_byte);
+
+            let input = InputEdit {
+                start_byte: edit.start_byte,
+                old_end_byte: edit.old_end_byte,
+                new_end_byte,
+                start_position,
+                old_end_position,
+                new_end_position,
+            };
+            tree.edit(&input);
+            retained.apply(&input);
+
+            // Las filas editadas antes se corren con las ediciones siguientes
+            let delta = new_end_position.row as isize - old_end_position.row as isize;
+            for rows in edited_rows.iter_mut() {
+                if *rows.start() > start_position.row {
+                    *rows = shift(*rows.start(), delta)..=shift(*rows.end(), delta);
+                }
+            }
+            edited_rows.push(rows_between(start_position, new_end_position));
+        }
+
+        let language = self.analyzer.language(&self.extension);
+        let new_tree = pool::parse_tree(language, &source, Some(&tree), &self.path)?;
+
+        let mut changed_rows = edited_rows;
+        for range in tree.changed_ranges(&new_tree) {
+            changed_rows.push(rows_between(range.start_point, range.end_point));
+        }
+
+        retained.check_nodes(&tree, &changed_rows);
+
+        // Tree-sitter puede recuperarse de un error de otra forma sin que
+        // cambie el texto, así que los nodos con errores se extraen siempre
+        let extract = |node: &Node| {
+            let rows = leading_row(node)..=node.end_position().row;
+            node.has_error()
+                || changed_rows.iter().any(|changed| overlaps(changed, &rows))
+                || retained.edited.iter().any(|bytes| overlaps(bytes, &(node.start_byte()..=node.end_byte())))
+        };
+        let root = new_tree.root_node();
+        let extracted_ranges: Vec<Range<usize>> = root.children(&mut root.walk())
+            .filter(|node| extract(node))
+            .map(|node| node.byte_range())
+            .collect();
+        let extracted = self.analyzer.analyze_tree_filtered(&new_tree, &source, &self.path, &self.roots, &extract)?;
+
+        let (new_result, extracted) = if same_imports(&self.result.imports, &extracted.imports) {
+            let mut new_result = extracted.clone();
+            retained.merge_into(&mut new_result, &extracted_ranges);
+            symbols::assign_symbol_ids(&mut new_result, &symbols::module_name(&self.path, &self.roots));
+            (new_result, extracted)
+        } else {
+            // Un import distinto puede cambiar cómo se resuelven las llamadas
+            // de cualquier función, así que se extrae todo de nuevo
+            let new_result = self.analyzer.analyze_tree(&new_tree, &source, &self.path, &self.roots)?;
+            (new_result.clone(), new_result)
+        };
+        let changes = diff_symbols(&self.result, &new_result, &extracted, &changed_rows);
+
+        self.source = source;
+        self.tree = new_tree;
+        self.result = new_result;
+        Ok(changes)
+    }
+}
+
+
+fn validate(source: &str, edit: &TextEdit) -> Result<(), AnalysisError> {
+    if edit.start_byte > edit.old_end_byte || edit.old_end_byte > source.len() {
+        return Err(AnalysisError::InvalidEdit(format!(
+            "range {}..{} is outside of the {} byte source", edit.start_byte, edit.old_end_byte, source.len()
+        )));
+    }
+    if !source.is_char_boundary(edit.start_byte) || !source.is_char_boundary(edit.old_end_byte) {
+        return Err(AnalysisError::InvalidEdit(format!(
+            "range {}..{} splits a UTF-8 character", edit.start_byte, edit.old_end_byte
+        )));
+    }
+    Ok(())
+}
+
+fn point_at(source: &str, byte: usize) -> Point {
+    let before = &source[..byte];
+    let row = before.matches('\n').count();
+    let column = before.rfind('\n').map(|i| byte - i - 1).unwrap_or(byte);
+    Point { row, column }
+}
+
+/// Filas que ocupa el rango `start..end`. Un rango que termina en la
+/// columna 0 (ej: texto terminado en salto de línea) no toca esa fila.
+fn rows_between(start: Point, end: Point) -> RangeInclusive<usize> {
+    if end.column == 0 && end.row > start.row {
+        start.row..=end.row - 1
+    } else {
+        start.row..=end.row
+    }
+}
+
+fn shift(row: usize, delta: isize) -> usize {
+    (row as isize + delta).max(0) as usize
+}
+
+fn overlaps(a: &RangeInclusive<usize>, b: &RangeInclusive<usize>) -> bool {
+    a.start() <= b.end() && b.start() <= a.end()
+}
+
+/// Los comentarios antes de una definición son su documentación (JSDoc);
+/// un error de sintaxis cuenta igual, porque puede ser un comentario a medio
+/// escribir.
+fn is_leading(node: &Node) -> bool {
+    node.kind() == "comment" || node.is_error()
+}
+
+/// Fila donde empiezan los comentarios que preceden a `node`.
+fn leading_row(node: &Node) -> usize {
+    let mut first = *node;
+    while let Some(previous) = first.prev_sibling()
+        && is_leading(&previous)
+    {
+        first = previous;
+    }
+    first.start_position().row
+}
+
+fn same_imports(a: &[ImportInfo], b: &[ImportInfo]) -> bool {
+    let key = |i: &ImportInfo| (i.name.clone(), i.specifier.clone(), i.alias.clone(), i.style, i.path.clone(), i.imported_names.clone());
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| key(a) == key(b))
+}
+
+
+/// Funciones y clases de nivel superior del análisis anterior, llevadas a
+/// las posiciones del texto editado. Las que una edición toca se descartan
+/// y en `edited` quedan los bytes donde puede empezar ahora lo que las
+/// reemplace, para volver a extraerlo.
+struct Retained {
+    functions: Vec<FunctionInfo>,
+    classes: Vec<ClassInfo>,
+    edited: Vec<RangeInclusive<usize>>,
+}
+
+impl Retained {
+    fn apply(&mut self, edit: &InputEdit) {
+        for bytes in self.edited.iter_mut() {
+            let end = if *bytes.end() > edit.start_byte && *bytes.end() < edit.old_end_byte {
+                edit.new_end_byte
+            } else {
+                shift_byte(*bytes.end(), edit)
+            };
+            *bytes = shift_byte(*bytes.start(), edit)..=end;
+        }
+
+        let edited = &mut self.edited;
+        let mut touches = |span: &Span, decorators: &[Decorator]| {
+            let start = decorators.iter().map(|d| d.span.start_byte).fold(span.start_byte, usize::min);
+            let touched = start < edit.old_end_byte && edit.start_byte < span.end_byte;
+            // Si la edición reemplazó el inicio del símbolo, lo que lo
+            // reemplace puede empezar en cualquier parte del texto nuevo
+            if touched && start < edit.start_byte {
+                edited.push(start..=start);
+            } else if touched {
+                edited.push(edit.start_byte..=edit.new_end_byte);
+            }
+            touched
+        };
+        self.functions.retain(|f| !touches(&f.span, &f.decorators));
+        self.classes.retain(|c| !touches(&c.span, &c.decorators));
+
+        for function in &mut self.functions {
+            shift_function(function, edit);
+        }
+        for class in &mut self.classes {
+            shift_class(class, edit);
+        }
+    }
+
+    /// Marca para volver a extraer los símbolos cuyos comentarios previos
+    /// cambiaron o que tienen errores de sintaxis. `tree` es el árbol
+    /// anterior con las ediciones aplicadas.
+    fn check_nodes(&mut self, tree: &Tree, changed_rows: &[RangeInclusive<usize>]) {
+        let root = tree.root_node();
+        let starts = self.functions.iter().map(|f| f.span.start_byte)
+            .chain(self.classes.iter().map(|c| c.span.start_byte));
+        for start in starts {
+            let Some(node) = root.children(&mut root.walk()).find(|c| c.byte_range().contains(&start)) else {
+                continue;
+            };
+            let rows = leading_row(&node)..=node.start_position().row;
+            if node.has_error() || changed_rows.iter().any(|changed| overlaps(changed, &rows)) {
+                self.edited.push(start..=start);
+            }
+        }
+    }
+
+    /// Agrega a `result` los símbolos conservados que no caen en un nodo que
+    /// se volvió a extraer, en orden de aparición. Un backend que no filtra
+    /// devuelve también los conservados, que se reconocen por su posición.
+    fn merge_into(self, result: &mut AnalysisResult, extracted: &[Range<usize>]) {
+        let starts: HashSet<usize> = result.functions.iter().map(|f| f.span.start_byte)
+            .chain(result.classes.iter().map(|c| c.span.start_byte))
+            .collect();
+        let fresh = |span: &Span| starts.contains(&span.start_byte)
+            || extracted.iter().any(|range| range.contains(&span.start_byte));
+        result.functions.extend(self.functions.into_iter().filter(|f| !fresh(&f.span)));
+        result.functions.sort_by_key(|f| f.span.start_byte);
+        result.classes.extend(self.classes.into_iter().filter(|c| !fresh(&c.span)));
+        result.classes.sort_by_key(|c| c.span.start_byte);
+    }
+}
+
+/// Posición de `byte` después de la edición; lo que estaba dentro del texto
+/// reemplazado queda en su inicio.
+fn shift_byte(byte: usize, edit: &InputEdit) -> usize {
+    if byte >= edit.old_end_byte {
+        byte - edit.old_end_byte + edit.new_end_byte
+    } else {
+        byte.min(edit.start_byte)
+    }
+}
+
+fn shift_line(line: &mut usize, byte: usize, edit: &InputEdit) {
+    if byte >= edit.old_end_byte {
+        *line = *line - edit.old_end_position.row + edit.new_end_position.row;
+    }
+}
+
+fn shift_span(span: &mut Span, edit: &InputEdit) {
+    let shift_point = |line: &mut usize, column: &mut usize, byte: &mut usize| {
+        if *byte < edit.old_end_byte {
+            return;
+        }
+        if *line - 1 == edit.old_end_position.row {
+            *column = *column - edit.old_end_position.column + edit.new_end_position.column;
+        }
+        shift_line(line, *byte, edit);
+        *byte = shift_byte(*byte, edit);
+    };
+    shift_point(&mut span.start_line, &mut span.start_column, &mut span.start_byte);
+    shift_point(&mut span.end_line, &mut span.end_column, &mut span.end_byte);
+}
+
+fn shift_decorators(decorators: &mut [Decorator], edit: &InputEdit) {
+    for decorator in decorators {
+        shift_line(&mut decorator.line, decorator.span.start_byte, edit);
+        shift_span(&mut decorator.span, edit);
+    }
+}
+
+fn shift_function(function: &mut FunctionInfo, edit: &InputEdit) {
+    shift_line(&mut function.line, function.span.start_byte, edit);
+    shift_line(&mut function.end_line, function.span.end_byte, edit);
+    shift_span(&mut function.span, edit);
+    shift_decorators(&mut function.decorators, edit);
+    for parameter in &mut function.parameters {
+        shift_span(&mut parameter.span, edit);
+    }
+    for call in function.function_calls.iter_mut().flatten() {
+        shift_line(&mut call.line, call.span.start_byte, edit);
+        shift_span(&mut call.span, edit);
+    }
+    for variable in &mut function.local_variables {
+        shift_line(&mut variable.line, variable.span.start_byte, edit);
+        shift_span(&mut variable.span, edit);
+    }
+    for nested in &mut function.functions {
+        shift_function(nested, edit);
+    }
+    for class in &mut function.classes {
+        shift_class(class, edit);
+    }
+}
+
+fn shift_class(class: &mut ClassInfo, edit: &InputEdit) {
+    shift_line(&mut class.line, class.span.start_byte, edit);
+    shift_span(&mut class.span, edit);
+    shift_decorators(&mut class.decorators, edit);
+    for base in &mut class.bases {
+        shift_span(&mut base.span, edit);
+    }
+    for keyword in &mut class.keywords {
+        shift_span(&mut keyword.span, edit);
+    }
+    for field in &mut class.fields {
+        shift_line(&mut field.line, field.span.start_byte, edit);
+        shift_span(&mut field.span, edit);
+    }
+    for method in &mut class.methods {
+        shift_function(method, edit);
+    }
+    for nested in &mut class.classes {
+        shift_class(nested, edit);
+    }
+}
+
+
+/// Rango de filas (base 0) de cada símbolo del análisis.
+struct Symbol<'a> {
+    rows: RangeInclusive<usize>,
+    function: Option<&'a FunctionInfo>,
+    documentation: Option<&'a str>,
+}
+
+fn collect_symbols(result: &AnalysisResult) -> BTreeMap<String, Symbol<'_>> {
+    let mut symbols = BTreeMap::new();
+    collect_nested(&result.functions, &result.classes, &mut symbols);
+    symbols
+}
+
+/// Las funciones y clases anidadas se identifican por su `qualified_name`
+/// (`Clase.metodo`, `externa.<locals>.interna`).
+fn collect_nested<'a>(functions: &'a [FunctionInfo], classes: &'a [ClassInfo], symbols: &mut BTreeMap<String, Symbol<'a>>) {
+    for function in functions {
+        symbols.insert(function.qualified_name.clone(), Symbol { rows: function_rows(function), function: Some(function), documentation: doc_text(&function.documentation) });
+        collect_nested(&function.functions, &function.classes, symbols);
+    }
+    for class in classes {
+        symbols.insert(class.qualified_name.clone(), Symbol { rows: decorated_rows(&class.span, &class.decorators), function: None, documentation: doc_text(&class.documentation) });
+        collect_nested(&class.methods, &class.classes, symbols);
+    }
+}
+
+fn doc_text(documentation: &Option<Documentation>) -> Option<&str> {
+    documentation.as_ref().map(|d| d.text.as_str())
+}
+
+fn function_rows(function: &FunctionInfo) -> RangeInclusive<usize> {
+    decorated_rows(&function.span, &function.decorators)
+}
+
+/// Los decoradores quedan antes del span de la definición; editarlos
+/// también modifica el símbolo.
+fn decorated_rows(span: &Span, decorators: &[Decorator]) -> RangeInclusive<usize> {
+    let rows = span_rows(span);
+    let first = decorators.iter().map(|d| d.span.start_line.saturating_sub(1)).min().unwrap_or(*rows.start());
+    first.min(*rows.start())..=*rows.end()
+}
+
+fn span_rows(span: &Span) -> RangeInclusive<usize> {
+    span.start_line.saturating_sub(1)..=span.end_line.saturating_sub(1)
+}
+
+/// Solo los símbolos que se volvieron a extraer (`extracted`) pueden haber
+/// cambiado; los conservados son los mismos del análisis anterior.
+fn diff_symbols(old: &AnalysisResult, new: &AnalysisResult, extracted: &AnalysisResult, changed_rows: &[RangeInclusive<usize>]) -> SymbolChanges {
+    let old_symbols = collect_symbols(old);
+    let new_symbols = collect_symbols(new);
+    let mut changes = SymbolChanges::default();
+
+    for (key, symbol) in collect_symbols(extracted) {
+        if let Some(previous) = old_symbols.get(&key) {
+            let touched = changed_rows.iter().any(|rows| overlaps(rows, &symbol.rows));
+            // Un import nuevo puede cambiar cómo se resuelven las llamadas
+            // de una función que no se editó
+            let reshaped = match (previous.function, symbol.function) {
+                (Some(a), Some(b)) => !same_shape(a, b),
+                _ => false,
+            };
+            // El JSDoc queda fuera del span
+            let redocumented = previous.documentation != symbol.documentation;
+            if touched || reshaped || redocumented {
+                changes.modified.push(key);
+            }
+        }
+    }
+    for key in new_symbols.keys() {
+        if !old_symbols.contains_key(key) {
+            changes.added.push(key.clone());
+        }
+    }
+    for key in old_symbols.keys() {
+        if !new_symbols.contains_key(key) {
+            changes.removed.push(key.clone());
+        }
+    }
+
+    changes
+}
+
+/// Compara dos funciones ignorando las posiciones en el archivo.
+fn same_shape(a: &FunctionInfo, b: &FunctionInfo) -> bool {
+    let params = |f: &FunctionInfo| f.parameters.iter()
+        .map(|p| (p.name.clone(), p.kind, p.param_type.clone(), p.default_value.clone()))
+        .collect::<Vec<_>>();
+    let calls = |f: &FunctionInfo| f.function_calls.iter().flatten()
+        .map(|c| (c.name.clone(), c.import_name.clone(), c.object_name.clone(), c.awaited))
+        .collect::<Vec<_>>();
+    let locals = |f: &FunctionInfo| f.local_variables.iter()
+        .map(|v| (v.name.clone(), v.assigned_from.clone()))
+        .collect::<Vec<_>>();
+
+    let decorators = |f: &FunctionInfo| f.decorators.iter()
+        .map(|d| (d.name.clone(), d.import_name.clone(), d.arguments.clone()))
+        .collect::<Vec<_>>();
+
+    a.return_type == b.return_type && params(a) == params(b) && calls(a) == calls(b) && locals(a) == locals(b)
+        && decorators(a) == decorators(b) && a.is_async == b.is_async && a.is_generator == b.is_generator
+}
@@ -0,0 +1,200 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter_test::{AnalysisCache, Analyzer, ProjectOptions};
+
+mod common;
+use common::temp_project;
+
+fn temp_cache(name: &str) -> AnalysisCache {
+    let cache = AnalysisCache::new(std::env::temp_dir().join(format!("tree_sitter_test_cachedir_{}", name)));
+    cache.clear().unwrap();
+    cache
+}
+
+#[test]
+fn test_second_run_hits_cache() {
+    let root = temp_project("cache_second_run", &[
+        ("main.py", "import utils\n\ndef main():\n    utils.helper()"),
+        ("utils.py", "def helper():\n    pass"),
+    ]);
+    let cache = temp_cache("second_run");
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    let first = analyzer.analyze_project(&root, &options).unwrap();
+    assert_eq!((cache.hits(), cache.misses()), (0, 2));
+
+    let second = analyzer.analyze_project(&root, &options).unwrap();
+    assert_eq!((cache.hits(), cache.misses()), (2, 2));
+    assert_eq!(
+        serde_json::to_value(&first.files).unwrap(),
+        serde_json::to_value(&second.files).unwrap()
+    );
+}
+
+#[test]
+fn test_changed_file_misses_only_itself() {
+    let root = temp_project("cache_changed_file", &[
+        ("a.py", "def a():\n    pass"),
+        ("b.py", "def b():\n    pass"),
+    ]);
+    let cache = temp_cache("changed_file");
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    analyzer.analyze_project(&root, &options).unwrap();
+    fs::write(root.join("b.py"), "def b2():\n    pass").unwrap();
+    let project = analyzer.analyze_project(&root, &options).unwrap();
+
+    assert_eq!((cache.hits(), cache.misses()), (1, 3));
+    assert_eq!(project.files[Path::new("b.py")].functions[0].name, "b2");
+}
+
+#[test]
+fn test_new_file_invalidates_import_resolution() {
+    let root = temp_project("cache_new_file", &[
+        ("main.py", "import utils\n\ndef main():\n    utils.helper()"),
+        ("other.py", "import os\n\ndef other():\n    pass"),
+    ]);
+    let cache = temp_cache("new_file");
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    let before = analyzer.analyze_project(&root, &options).unwrap();
+    assert!(before.files[Path::new("main.py")].imports[0].path.is_none());
+
+    fs::write(root.join("utils.py"), "def helper():\n    pass").unwrap();
+    let after = analyzer.analyze_project(&root, &options).unwrap();
+
+    // Solo `main.py` consultó `utils.py`; `other.py` sigue en el caché
+    assert_eq!((cache.hits(), cache.misses()), (1, 4));
+    assert!(after.files[Path::new("main.py")].imports[0].path.is_some());
+}
+
+#[test]
+fn test_unrelated_new_files_keep_entries() {
+    let root = temp_project("cache_unrelated_files", &[
+        ("main.py", "import utils\n\ndef main():\n    utils.helper()"),
+        ("utils.py", "def helper():\n    pass"),
+        ("web/app.ts", "import { a } from './a';"),
+        ("web/a.ts", "export const a = 1;"),
+    ]);
+    let cache = temp_cache("unrelated_files");
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    analyzer.analyze_project(&root, &options).unwrap();
+    fs::write(root.join("README.md"), "# proyecto").unwrap();
+    fs::write(root.join("docs.txt"), "notas").unwrap();
+    fs::remove_file(root.join("web/a.ts")).unwrap();
+    analyzer.analyze_project(&root, &options).unwrap();
+
+    // Borrar `web/a.ts` solo invalida a quien lo importaba
+    assert_eq!((cache.hits(), cache.misses()), (2, 5));
+}
+
+#[test]
+fn test_cache_dir_inside_project_is_ignored() {
+    let root = temp_project("cache_inside_project", &[
+        ("main.py", "def main():\n    pass"),
+    ]);
+    let cache = AnalysisCache::new(root.join(".cache"));
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    analyzer.analyze_project(&root, &options).unwrap();
+    analyzer.analyze_project(&root, &options).unwrap();
+
+    assert_eq!(cache.hits(), 1);
+}
+
+#[test]
+fn test_key_depends_on_language_and_source() {
+    let cache = temp_cache("key");
+    let path = Path::new("a.py");
+    let roots = vec![PathBuf::from("/project")];
+
+    let key = cache.key("x = 1", path, "python", &roots);
+    assert_eq!(key, cache.key("x = 1", path, "python", &roots));
+    assert_ne!(key, cache.key("x = 2", path, "python", &roots));
+    assert_ne!(key, cache.key("x = 1", path, "typescript", &roots));
+    assert_ne!(key, cache.key("x = 1", path, "python", &[]));
+    assert_ne!(key, cache.key("x = 1", Path::new("b.py"), "python", &roots));
+}
+
+#[test]
+fn test_tsconfig_change_invalidates_entries() {
+    let root = temp_project("cache_tsconfig", &[
+        ("tsconfig.json", r#"{ "compilerOptions": { "paths": { "@lib/*": ["old/*"] } } }"#),
+        ("main.ts", "import { a } from '@lib/a';"),
+        ("old/a.ts", "export const a = 1;"),
+        ("new/a.ts", "export const a = 2;"),
+    ]);
+    let cache = temp_cache("tsconfig");
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    analyzer.analyze_project(&root, &options).unwrap();
+    fs::write(root.join("tsconfig.json"), r#"{ "compilerOptions": { "paths": { "@lib/*": ["new/*"] } } }"#).unwrap();
+    let project = analyzer.analyze_project(&root, &options).unwrap();
+
+    assert_eq!(cache.hits(), 0);
+    let path = project.files[Path::new("main.ts")].imports[0].path.clone().unwrap();
+    assert!(path.ends_with("new/a.ts"));
+}
+
+#[test]
+fn test_package_json_change_invalidates_entries() {
+    let root = temp_project("cache_package_json", &[
+        ("main.ts", "import { a } from 'lib';"),
+        ("node_modules/lib/package.json", r#"{ "name": "lib", "main": "old.js" }"#),
+        ("node_modules/lib/old.js", "exports.a = 1;"),
+        ("node_modules/lib/new.js", "exports.a = 2;"),
+    ]);
+    let cache = temp_cache("package_json");
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    analyzer.analyze_project(&root, &options).unwrap();
+    fs::write(root.join("node_modules/lib/package.json"), r#"{ "name": "lib", "main": "new.js" }"#).unwrap();
+    let project = analyzer.analyze_project(&root, &options).unwrap();
+
+    // Los `.js` del paquete no dependen del `package.json`; `main.ts` sí
+    assert_eq!((cache.hits(), cache.misses()), (2, 4));
+    let path = project.files[Path::new("main.ts")].imports[0].path.clone().unwrap();
+    assert!(path.ends_with("node_modules/lib/new.js"));
+}
+
+#[test]
+fn test_gitignored_package_json_change_invalidates_entries() {
+    let root = temp_project("cache_package_json_gitignored", &[
+        (".gitignore", "node_modules/\n"),
+        ("main.ts", "import { a } from 'lib';"),
+        ("node_modules/lib/package.json", r#"{ "name": "lib", "main": "old.js" }"#),
+        ("node_modules/lib/old.js", "exports.a = 1;"),
+        ("node_modules/lib/new.js", "exports.a = 2;"),
+    ]);
+    let cache = temp_cache("package_json_gitignored");
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let options = ProjectOptions::new().cache(cache.clone());
+
+    analyzer.analyze_project(&root, &options).unwrap();
+    fs::write(root.join("node_modules/lib/package.json"), r#"{ "name": "lib", "main": "new.js" }"#).unwrap();
+    let project = analyzer.analyze_project(&root, &options).unwrap();
+
+    assert_eq!(cache.hits(), 0);
+    let path = project.files[Path::new("main.ts")].imports[0].path.clone().unwrap();
+    assert!(path.ends_with("node_modules/lib/new.js"));
+}
+
+#[test]
+fn test_short_keys_are_valid() {
+    let cache = temp_cache("short_keys");
+    let result = Analyzer::new(vec![]).analyze_source("def f():\n    pass", Path::new("a.py")).unwrap();
+
+    for key in ["", "k", "ñ", "../x"] {
+        assert!(cache.get(key).is_none());
+        cache.put(key, &result, &[]).unwrap();
+        assert_eq!(cache.get(key).unwrap().functions[0].name, "f");
+    }
+}
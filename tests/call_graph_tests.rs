@@ -0,0 +1,371 @@
+use std::path::Path;
+use tree_sitter_test::models::call_graph::{CallEdge, CallGraph, CallTarget};
+use tree_sitter_test::{Analyzer, ProjectOptions};
+
+mod common;
+use common::temp_project;
+
+fn build(name: &str, files: &[(&str, &str)]) -> CallGraph {
+    let root = temp_project(name, files);
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let project = analyzer.analyze_project(&root, &ProjectOptions::new()).unwrap();
+    analyzer.call_graph(&project)
+}
+
+fn edge<'a>(graph: &'a CallGraph, caller: &str, name: &str) -> &'a CallEdge {
+    graph.edges.iter()
+        .find(|e| e.caller.function.as_deref() == Some(caller) && e.name == name)
+        .unwrap_or_else(|| panic!("no edge {} -> {}", caller, name))
+}
+
+fn resolved_to(edge: &CallEdge, file: &str, class: Option<&str>, function: Option<&str>) {
+    match &edge.callee {
+        CallTarget::Resolved(target) => {
+            assert_eq!(target.file, Path::new(file));
+            assert_eq!(target.class.as_deref(), class);
+            assert_eq!(target.function.as_deref(), function);
+        }
+        other => panic!("expected resolved edge, got {:?}", other),
+    }
+}
+
+// ---------------------------- Python ----------------------------
+
+const PY_MAIN: &str = "\
+from utils import helper
+import os
+import helpers
+from models import Geometry
+
+def main():
+    helper()
+    helpers.format_name('x')
+    os.getcwd()
+    print('x')
+    missing()
+    g = Geometry('circle')
+    g.describe()
+    local()
+
+def local():
+    pass
+";
+
+const PY_MODELS: &str = "\
+class Geometry:
+    def __init__(self, name):
+        self.name = name
+
+    def describe(self):
+        return self.area()
+
+    def area(self):
+        return 0
+";
+
+fn python_graph(name: &str) -> CallGraph {
+    build(name, &[
+        ("main.py", PY_MAIN),
+        ("utils.py", "def helper():\n    pass"),
+        ("helpers.py", "def format_name(n):\n    return n"),
+        ("models.py", PY_MODELS),
+    ])
+}
+
+#[test]
+fn test_from_import_call_resolves_to_definition() {
+    let graph = python_graph("graph_py_from_import");
+
+    let e = edge(&graph, "main", "helper");
+    resolved_to(e, "utils.py", None, Some("helper"));
+    assert_eq!(e.caller.file, Path::new("main.py"));
+    assert_eq!(e.line, 7);
+}
+
+#[test]
+fn test_module_call_resolves_to_definition() {
+    let graph = python_graph("graph_py_module_call");
+
+    resolved_to(edge(&graph, "main", "format_name"), "helpers.py", None, Some("format_name"));
+}
+
+#[test]
+fn test_class_instantiation_and_method_call() {
+    let graph = python_graph("graph_py_class");
+
+    resolved_to(edge(&graph, "main", "Geometry"), "models.py", Some("Geometry"), None);
+    resolved_to(edge(&graph, "main", "describe"), "models.py", Some("Geometry"), Some("describe"));
+}
+
+#[test]
+fn test_self_call_resolves_to_sibling_method() {
+    let graph = python_graph("graph_py_self");
+
+    let e = edge(&graph, "describe", "area");
+    assert_eq!(e.caller.class.as_deref(), Some("Geometry"));
+    resolved_to(e, "models.py", Some("Geometry"), Some("area"));
+}
+
+#[test]
+fn test_same_file_call() {
+    let graph = python_graph("graph_py_local");
+
+    let e = edge(&graph, "main", "local");
+    resolved_to(e, "main.py", None, Some("local"));
+    if let CallTarget::Resolved(target) = &e.callee {
+        assert_eq!(target.line, 16);
+    }
+}
+
+#[test]
+fn test_unresolved_edges_are_labelled() {
+    let graph = python_graph("graph_py_unresolved");
+
+    assert_eq!(edge(&graph, "main", "getcwd").callee, CallTarget::External { module: "os".to_string() });
+    assert_eq!(edge(&graph, "main", "print").callee, CallTarget::Builtin);
+    assert_eq!(edge(&graph, "main", "missing").callee, CallTarget::Unknown);
+}
+
+#[test]
+fn test_aliased_imports_resolve_to_definition() {
+    let graph = build("graph_py_aliases", &[
+        ("main.py", "\
+import utils as u
+from models import Geometry as Shape, area as compute_area
+
+def main():
+    u.helper()
+    compute_area(1)
+    s = Shape()
+    s.describe()
+"),
+        ("utils.py", "def helper():\n    pass"),
+        ("models.py", "\
+class Geometry:
+    def describe(self):
+        pass
+
+def area(r):
+    pass
+"),
+    ]);
+
+    resolved_to(edge(&graph, "main", "helper"), "utils.py", None, Some("helper"));
+    resolved_to(edge(&graph, "main", "area"), "models.py", None, Some("area"));
+    resolved_to(edge(&graph, "main", "describe"), "models.py", Some("Geometry"), Some("describe"));
+}
+
+#[test]
+fn test_nested_functions_and_classes() {
+    let graph = build("graph_py_nested", &[
+        ("main.py", "\
+from utils import helper
+
+def outer():
+    def inner():
+        helper()
+    class Local:
+        def run(self):
+            pass
+    inner()
+    obj = Local()
+    obj.run()
+
+class Outer:
+    class Inner:
+        def m(self):
+            helper()
+"),
+        ("utils.py", "def helper():\n    pass"),
+    ]);
+
+    let calls: Vec<&str> = graph.edges.iter()
+        .filter(|e| e.caller.qualified_name == "outer")
+        .map(|e| e.name.as_str())
+        .collect();
+    assert_eq!(calls, vec!["inner", "Local", "run"]);
+
+    let e = edge(&graph, "outer", "inner");
+    resolved_to(e, "main.py", None, Some("inner"));
+    if let CallTarget::Resolved(target) = &e.callee {
+        assert_eq!(target.qualified_name, "outer.<locals>.inner");
+    }
+    resolved_to(edge(&graph, "outer", "run"), "main.py", Some("Local"), Some("run"));
+
+    let e = edge(&graph, "inner", "helper");
+    assert_eq!(e.caller.qualified_name, "outer.<locals>.inner");
+    resolved_to(e, "utils.py", None, Some("helper"));
+
+    let e = edge(&graph, "m", "helper");
+    assert_eq!(e.caller.class.as_deref(), Some("Inner"));
+    assert_eq!(e.caller.qualified_name, "Outer.Inner.m");
+    resolved_to(e, "utils.py", None, Some("helper"));
+}
+
+#[test]
+fn test_unresolved_relative_import_is_unknown() {
+    let graph = build("graph_py_missing_relative", &[
+        ("pkg/__init__.py", ""),
+        ("pkg/main.py", "from .missing import f\nimport requests\n\ndef main():\n    f()\n    requests.get('x')\n"),
+    ]);
+
+    assert_eq!(edge(&graph, "main", "f").callee, CallTarget::Unknown);
+    assert_eq!(edge(&graph, "main", "get").callee, CallTarget::External { module: "requests".to_string() });
+}
+
+#[test]
+fn test_ambiguous_import_binding_is_unknown() {
+    let graph = build("graph_py_ambiguous", &[
+        ("a/__init__.py", ""),
+        ("a/mod.py", "def f():\n    pass\n"),
+        ("b/__init__.py", ""),
+        ("b/mod.py", "def f():\n    pass\n"),
+        ("main.py", "from a import mod\nfrom b import mod\n\ndef main():\n    mod.f()\n"),
+        ("single.py", "from a import mod\n\ndef run():\n    mod.f()\n"),
+    ]);
+
+    assert_eq!(edge(&graph, "main", "f").callee, CallTarget::Unknown);
+    resolved_to(edge(&graph, "run", "f"), "a/mod.py", None, Some("f"));
+}
+
+// ---------------------------- TypeScript ----------------------------
+
+#[test]
+fn test_typescript_cross_file_calls() {
+    let graph = build("graph_ts", &[
+        ("src/app.ts", "\
+import { add } from './math';
+import * as strings from './strings';
+import { readFile } from 'fs';
+
+function run(): void {
+    add(1, 2);
+    strings.upper('a');
+    readFile('x');
+    console.log('done');
+}"),
+        ("src/math.ts", "export function add(a: number, b: number): number { return a + b; }"),
+        ("src/strings.ts", "export const upper = (s: string): string => s.toUpperCase();"),
+    ]);
+
+    resolved_to(edge(&graph, "run", "add"), "src/math.ts", None, Some("add"));
+    resolved_to(edge(&graph, "run", "upper"), "src/strings.ts", None, Some("upper"));
+    assert!(matches!(&edge(&graph, "run", "readFile").callee, CallTarget::External { .. }));
+    assert_eq!(edge(&graph, "run", "log").callee, CallTarget::Builtin);
+}
+
+#[test]
+fn test_typescript_aliased_and_default_imports() {
+    let graph = build("graph_ts_aliases", &[
+        ("src/app.ts", "\
+import render, { add as plus } from './math';
+import { Shape as Figure } from './shapes';
+
+function run(): void {
+    plus(1, 2);
+    render();
+    const f = new Figure();
+    f.area();
+}"),
+        ("src/math.ts", "\
+export function add(a: number, b: number): number { return a + b; }
+export default function render(): void {}"),
+        ("src/shapes.ts", "export class Shape { area(): number { return 0; } }"),
+    ]);
+
+    resolved_to(edge(&graph, "run", "add"), "src/math.ts", None, Some("add"));
+    resolved_to(edge(&graph, "run", "render"), "src/math.ts", None, Some("render"));
+    resolved_to(edge(&graph, "run", "area"), "src/shapes.ts", Some("Shape"), Some("area"));
+}
+
+#[test]
+fn test_typescript_unresolved_relative_import_is_unknown() {
+    let graph = build("graph_ts_missing_relative", &[
+        ("src/app.ts", "import { g } from './nope';\nimport { h } from 'lodash';\n\nfunction run(): void {\n    g();\n    h();\n}"),
+    ]);
+
+    assert_eq!(edge(&graph, "run", "g").callee, CallTarget::Unknown);
+    assert_eq!(edge(&graph, "run", "h").callee, CallTarget::External { module: "lodash".to_string() });
+}
+
+#[test]
+fn test_named_import_used_as_namespace() {
+    let graph = build("graph_ts_named_namespace", &[
+        ("src/app.ts", "\
+import { utils, helpers } from './lib';
+
+function run(): void {
+    utils.f();
+    helpers.f();
+}"),
+        ("src/lib.ts", "\
+export function f(): void {}
+export class utils { static f(): void {} }
+export const helpers = { f };"),
+    ]);
+
+    let calls: Vec<_> = graph.edges.iter().filter(|e| e.name == "f").collect();
+    assert_eq!(calls.len(), 2);
+    // `utils.f()` es el método estático de la clase exportada, no la `f` del módulo
+    resolved_to(calls[0], "src/lib.ts", Some("utils"), Some("f"));
+    assert_eq!(calls[1].callee, CallTarget::Unknown);
+}
+
+#[test]
+fn test_require_resolves_to_definition() {
+    let graph = build("graph_js_require", &[
+        ("src/app.js", "\
+const math = require('./math');
+const { upper } = require('./strings');
+
+function run() {
+    math.add(1, 2);
+    upper('a');
+}"),
+        ("src/math.js", "function add(a, b) { return a + b; }\nmodule.exports = { add };"),
+        ("src/strings.js", "function upper(s) { return s.toUpperCase(); }\nmodule.exports = { upper };"),
+    ]);
+
+    resolved_to(edge(&graph, "run", "add"), "src/math.js", None, Some("add"));
+    resolved_to(edge(&graph, "run", "upper"), "src/strings.js", None, Some("upper"));
+}
+
+#[test]
+fn test_callers_of() {
+    let graph = python_graph("graph_py_callers");
+
+    let callers: Vec<_> = graph.callers_of(Path::new("utils.py"), "helper").collect();
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].caller.function.as_deref(), Some("main"));
+}
+
+#[test]
+fn test_calls_from_and_callers_of_use_qualified_names() {
+    let graph = build("graph_py_qualified", &[
+        ("main.py", "\
+def a():
+    def helper():
+        return first()
+    return helper()
+
+def b():
+    def helper():
+        return second()
+    return helper()
+
+def first():
+    pass
+
+def second():
+    pass
+"),
+    ]);
+
+    let calls: Vec<_> = graph.calls_from(Path::new("main.py"), "a.<locals>.helper").map(|e| e.name.as_str()).collect();
+    assert_eq!(calls, ["first"]);
+
+    let callers: Vec<_> = graph.callers_of(Path::new("main.py"), "b.<locals>.helper").collect();
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].caller.qualified_name, "b");
+    assert_eq!(graph.callers_of(Path::new("main.py"), "helper").count(), 0);
+}
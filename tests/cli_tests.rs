@@ -0,0 +1,68 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use common::temp_project;
+
+fn run_binary(dir: &Path, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_tree-sitter"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+// ---------------------------- Archivos ----------------------------
+
+#[test]
+fn test_same_file_name_in_different_folders() {
+    let dir = temp_project("cli_same_name", &[
+        ("input-files/a/utils.py", "def helper():\n    return 1\n"),
+        ("input-files/b/utils.py", "from a.utils import helper\n\ndef run():\n    return helper()\n"),
+    ]);
+
+    run_binary(&dir, &["a/utils.py"]);
+    run_binary(&dir, &["b/utils.py"]);
+
+    let a = read_json(&dir.join("parsed-files/a/utils.py.json"));
+    let b = read_json(&dir.join("parsed-files/b/utils.py.json"));
+    assert_eq!(a["functions"][0]["name"], "helper");
+    assert_eq!(b["functions"][0]["name"], "run");
+    // b/ resuelve imports de a/ porque la raíz sigue siendo input-files/
+    assert!(b["imports"][0]["path"].as_str().unwrap().ends_with("utils.py"), "{}", b["imports"][0]);
+}
+
+#[test]
+fn test_path_inside_input_files_keeps_its_folder() {
+    let dir = temp_project("cli_explicit_path", &[("input-files/a/utils.py", "def helper():\n    return 1\n")]);
+
+    run_binary(&dir, &["input-files/a/utils.py"]);
+
+    assert!(dir.join("parsed-files/a/utils.py.json").exists());
+}
+
+// ---------------------------- Grafo de llamadas ----------------------------
+
+#[test]
+fn test_call_graph_for_single_file() {
+    let dir = temp_project("cli_file_call_graph", &[
+        ("input-files/a/utils.py", "def helper():\n    return 1\n\ndef run():\n    return helper()\n"),
+    ]);
+
+    run_binary(&dir, &["a/utils.py", "--call-graph"]);
+
+    assert!(dir.join("parsed-files/a/utils.py.json").exists());
+    let graph = read_json(&dir.join("parsed-files/call-graph.json"));
+    let edges = graph["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 1, "{}", graph);
+    assert_eq!(edges[0]["callee"]["kind"], "resolved");
+    assert_eq!(edges[0]["callee"]["qualified_name"], "helper");
+}
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use tree_sitter_test::models::import_info::ImportInfo;
+
+/// Crea (o recrea) un proyecto en el directorio temporal con los archivos
+/// dados, como pares de ruta relativa y contenido.
+pub fn temp_project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tree_sitter_test_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    for (path, content) in files {
+        let full = dir.join(path);
+        fs::create_dir_all(full.parent().unwrap()).unwrap();
+        fs::write(full, content).unwrap();
+    }
+    dir
+}
+
+/// Como `temp_project`, con la ruta canónica: los paths resueltos de los
+/// imports y los nombres de módulo se calculan sobre rutas canónicas, y el
+/// directorio temporal puede ser un symlink (`/tmp` en macOS).
+pub fn canonical_temp_project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    temp_project(name, files).canonicalize().unwrap()
+}
+
+/// Nombres originales importados, sin alias.
+pub fn names(import: &ImportInfo) -> Vec<&str> {
+    import.imported_names.iter().map(|n| n.name.as_str()).collect()
+}
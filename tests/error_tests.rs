@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter_test::error::AnalysisError;
+use tree_sitter_test::parser::{parse_file, python, typescript};
+use tree_sitter_test::run_analysis;
+
+// ---------------------------- parse_file ----------------------------
+
+#[test]
+fn test_unsupported_extension_is_an_error() {
+    let result = parse_file("puts 'hola'", Path::new("script.rb"), &[]);
+
+    assert!(matches!(result, Err(AnalysisError::UnsupportedLanguage(p)) if p == Path::new("script.rb")));
+}
+
+#[test]
+fn test_missing_extension_is_an_error() {
+    let result = parse_file("", Path::new("Makefile"), &[]);
+
+    assert!(matches!(result, Err(AnalysisError::UnsupportedLanguage(_))));
+}
+
+// ---------------------------- Parsers ----------------------------
+
+#[test]
+fn test_python_syntax_errors_do_not_panic() {
+    let source = "import\ndef broken(x = :\n    return (\nclass";
+    let result = python::parse(source, Path::new("broken.py"), &[]);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_python_default_parameter_without_value_does_not_panic() {
+    let source = "def f(a: int = , b = ):\n    pass";
+    let result = python::parse(source, Path::new("broken.py"), &[]);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_typescript_syntax_errors_do_not_panic() {
+    let source = "import { from ; function (a: , ) { class {";
+    let result = typescript::parse(source, Path::new("broken.ts"), &[]);
+
+    assert!(result.is_ok());
+}
+
+// ---------------------------- run_analysis ----------------------------
+
+#[test]
+fn test_missing_file_is_io_error() {
+    let result = run_analysis(Path::new("does/not/exist.py"), &[]);
+
+    assert!(matches!(result, Err(AnalysisError::Io { .. })));
+}
+
+#[test]
+fn test_invalid_utf8_file() {
+    let dir = std::env::temp_dir().join("tree_sitter_test_invalid_utf8");
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("latin1.py");
+    fs::write(&file, [0x78, 0x20, 0x3d, 0x20, 0x27, 0xe9, 0x27]).unwrap();
+
+    let result = run_analysis(&file, &[PathBuf::from(&dir)]);
+
+    assert!(matches!(result, Err(AnalysisError::InvalidUtf8(_))));
+}
+
+#[test]
+fn test_error_messages() {
+    let err = AnalysisError::UnsupportedLanguage(PathBuf::from("a.rb"));
+    assert_eq!(err.to_string(), "Unsupported file type: a.rb");
+}
@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+use tree_sitter_test::models::import_info::ImportedName;
+use tree_sitter_test::models::parameter_info::ParameterKind;
+use tree_sitter_test::parser::parse_file;
+
+fn js_path() -> PathBuf {
+    PathBuf::from("test_file.js")
+}
+
+fn jsx_path() -> PathBuf {
+    PathBuf::from("test_file.jsx")
+}
+
+fn dummy_roots() -> Vec<PathBuf> {
+    vec![]
+}
+
+// ---------------------------- Imports ----------------------------
+
+#[test]
+fn test_es_module_imports() {
+    let source = "import React, { useState as state } from 'react';\nimport * as utils from './utils';";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 2);
+    assert_eq!(result.imports[0].name, "react");
+    assert_eq!(result.imports[0].imported_names[0], ImportedName::default_export("React"));
+    assert_eq!(result.imports[0].imported("state").map(|n| n.name.as_str()), Some("useState"));
+    assert_eq!(result.imports[1].imported_names, vec![ImportedName::namespace("utils")]);
+}
+
+// ---------------------------- Functions ----------------------------
+
+#[test]
+fn test_function_parameters() {
+    let source = "function connect(host, port = 8080, { timeout }, ...rest) {}";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    let params = &result.functions[0].parameters;
+    let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["host", "port", "{ timeout }", "...rest"]);
+    assert_eq!(params[1].default_value.as_deref(), Some("8080"));
+    assert!(params.iter().all(|p| p.param_type.is_none()));
+    assert_eq!(params[3].kind, ParameterKind::VarPositional);
+    assert!(params[..3].iter().all(|p| p.kind == ParameterKind::PositionalOrKeyword));
+}
+
+#[test]
+fn test_arrow_function_const() {
+    let source = "const add = (a, b) => sum(a, b);";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.functions.len(), 1);
+    assert_eq!(result.functions[0].name, "add");
+    assert_eq!(result.functions[0].parameters.len(), 2);
+    assert_eq!(result.functions[0].function_calls.as_ref().unwrap()[0].name, "sum");
+}
+
+#[test]
+fn test_comparison_is_not_a_generic_call() {
+    // En TypeScript `a < b > (c)` es una llamada con argumento de tipo
+    let source = "function check(a, b, c) {\n    return a < b > (c);\n}";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    assert!(result.functions[0].function_calls.as_ref().unwrap().is_empty());
+}
+
+// ---------------------------- Classes ----------------------------
+
+#[test]
+fn test_class_methods_and_field_arrows() {
+    let source = "\
+class Counter {
+    count = 0;
+    increment = () => this.update(1);
+    update(delta) {
+        log(delta);
+    }
+}";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.classes.len(), 1);
+    let names: Vec<&str> = result.classes[0].methods.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["increment", "update"]);
+}
+
+#[test]
+fn test_jsdoc_types_on_untyped_parameters() {
+    let source = "\
+class Cache {
+    /** @type {Map<string, number>} */
+    #entries = new Map();
+
+    /**
+     * Guarda un valor.
+     * @param {string} key Clave.
+     * @param {number} [ttl=60] Segundos de vida.
+     */
+    set(key, ttl = 60) {}
+}";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    let field = &result.classes[0].fields[0];
+    assert_eq!(field.name, "#entries");
+    let tag = &field.documentation.as_ref().unwrap().tags[0];
+    assert_eq!((tag.name.as_str(), tag.value.as_str()), ("type", "{Map<string, number>}"));
+
+    let set = &result.classes[0].methods[0];
+    let doc = set.documentation.as_ref().unwrap();
+    assert_eq!(doc.params[1].param_type.as_deref(), Some("number"));
+    let descriptions: Vec<Option<&str>> = set.parameters.iter().map(|p| p.description.as_deref()).collect();
+    assert_eq!(descriptions, vec![Some("Clave."), Some("Segundos de vida.")]);
+}
+
+// ---------------------------- JSX ----------------------------
+
+#[test]
+fn test_jsx_in_js_and_jsx_files() {
+    let source = "\
+function App() {
+    return <div onClick={() => track('click')}>{render()}</div>;
+}";
+    for path in [js_path(), jsx_path()] {
+        let result = parse_file(source, &path, &dummy_roots()).unwrap();
+
+        assert_eq!(result.functions.len(), 1, "{}", path.display());
+        let calls: Vec<&str> = result.functions[0].function_calls.as_ref().unwrap().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(calls, vec!["track", "render"], "{}", path.display());
+    }
+}
@@ -0,0 +1,325 @@
+use std::fs;
+use std::path::Path;
+use tree_sitter_test::Analyzer;
+use tree_sitter_test::parser::probe;
+use tree_sitter_test::parser::tsconfig::TsConfig;
+use tree_sitter_test::parser::typescript::resolve_ts_import;
+use tree_sitter_test::models::import_info::{ImportInfo, ResolutionRule};
+
+mod common;
+use common::canonical_temp_project;
+
+fn imports_of(root: &Path, file: &str) -> Vec<ImportInfo> {
+    Analyzer::new(vec![root.to_path_buf()]).analyze_file(&root.join(file)).unwrap().imports
+}
+
+fn resolved(import: &ImportInfo, root: &Path, file: &str, rule: ResolutionRule) {
+    assert_eq!(import.path.as_deref(), Some(root.join(file).as_path()), "import {}", import.name);
+    assert_eq!(import.resolved_by, Some(rule), "import {}", import.name);
+}
+
+// ---------------------------- tsconfig ----------------------------
+
+#[test]
+fn test_paths_wildcard_through_extends_chain() {
+    let root = canonical_temp_project("resolve_paths_extends", &[
+        ("tsconfig.json", r#"{ "extends": "./config/base", "compilerOptions": { "strict": true } }"#),
+        ("config/base.json", r#"{
+            // Los alias viven en la configuración compartida
+            "compilerOptions": {
+                "baseUrl": "..",
+                "paths": {
+                    "@app/*": ["src/app/*"],
+                    "@shared": ["src/shared/index.ts"],
+                },
+            },
+        }"#),
+        ("src/main.ts", "import { UserService } from '@app/services/user';\nimport { log } from '@shared';"),
+        ("src/app/services/user.ts", "export class UserService {}"),
+        ("src/shared/index.ts", "export function log() {}"),
+    ]);
+
+    let imports = imports_of(&root, "src/main.ts");
+    resolved(&imports[0], &root, "src/app/services/user.ts", ResolutionRule::Paths { pattern: "@app/*".to_string() });
+    resolved(&imports[1], &root, "src/shared/index.ts", ResolutionRule::Paths { pattern: "@shared".to_string() });
+}
+
+#[test]
+fn test_most_specific_paths_pattern_wins() {
+    let root = canonical_temp_project("resolve_paths_specific", &[
+        ("tsconfig.json", r#"{ "compilerOptions": { "paths": {
+            "@lib/*": ["lib/*"],
+            "@lib/internal/*": ["internal/*"]
+        } } }"#),
+        ("main.ts", "import { a } from '@lib/internal/a';\nimport { b } from '@lib/b';"),
+        ("internal/a.ts", "export const a = 1;"),
+        ("lib/b.ts", "export const b = 1;"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "internal/a.ts", ResolutionRule::Paths { pattern: "@lib/internal/*".to_string() });
+    resolved(&imports[1], &root, "lib/b.ts", ResolutionRule::Paths { pattern: "@lib/*".to_string() });
+}
+
+#[test]
+fn test_base_url() {
+    let root = canonical_temp_project("resolve_base_url", &[
+        ("web/tsconfig.json", r#"{ "compilerOptions": { "baseUrl": "src" } }"#),
+        ("web/src/pages/home.ts", "import { format } from 'utils/format';"),
+        ("web/src/utils/format.ts", "export function format() {}"),
+    ]);
+
+    let imports = imports_of(&root, "web/src/pages/home.ts");
+    resolved(&imports[0], &root, "web/src/utils/format.ts", ResolutionRule::BaseUrl);
+}
+
+#[test]
+fn test_root_dirs() {
+    let root = canonical_temp_project("resolve_root_dirs", &[
+        ("tsconfig.json", r#"{ "compilerOptions": { "rootDirs": ["src", "generated"] } }"#),
+        ("src/views/main.ts", "import { template } from './template';"),
+        ("generated/views/template.ts", "export const template = '';"),
+    ]);
+
+    let imports = imports_of(&root, "src/views/main.ts");
+    resolved(&imports[0], &root, "generated/views/template.ts", ResolutionRule::RootDirs);
+}
+
+#[test]
+fn test_relative_and_project_root_rules() {
+    let root = canonical_temp_project("resolve_ts_plain", &[
+        ("src/main.ts", "import { a } from './a';\nimport { b } from 'src/b';\nimport { c } from 'missing';"),
+        ("src/a.ts", "export const a = 1;"),
+        ("src/b.ts", "export const b = 1;"),
+    ]);
+
+    let imports = imports_of(&root, "src/main.ts");
+    resolved(&imports[0], &root, "src/a.ts", ResolutionRule::Relative);
+    resolved(&imports[1], &root, "src/b.ts", ResolutionRule::ProjectRoot);
+    assert!(imports[2].path.is_none());
+    assert!(imports[2].resolved_by.is_none());
+}
+
+#[test]
+fn test_resolve_ts_import_follows_tsconfig() {
+    let root = canonical_temp_project("resolve_ts_import_fn", &[
+        ("tsconfig.json", r#"{ "compilerOptions": { "baseUrl": "src" } }"#),
+        ("src/main.ts", ""),
+        ("src/a.ts", "export const a = 1;"),
+        ("src/utils/format.ts", "export function format() {}"),
+    ]);
+    let roots = [root.clone()];
+    let main = root.join("src/main.ts");
+
+    assert_eq!(resolve_ts_import(&main, "./a", &roots), Some(root.join("src/a.ts")));
+    assert_eq!(resolve_ts_import(&main, "utils/format", &roots), Some(root.join("src/utils/format.ts")));
+    assert_eq!(resolve_ts_import(&main, "missing", &roots), None);
+}
+
+#[test]
+fn test_loaded_tsconfig_is_reused_and_reloaded_on_change() {
+    let root = canonical_temp_project("resolve_tsconfig_memo", &[
+        ("tsconfig.json", r#"{ "extends": "./base.json" }"#),
+        ("base.json", r#"{ "compilerOptions": { "paths": { "@lib/*": ["old/*"] } } }"#),
+    ]);
+    let tsconfig = root.join("tsconfig.json");
+
+    let (first, first_probes) = probe::record(|| TsConfig::load(&tsconfig));
+    let (second, second_probes) = probe::record(|| TsConfig::load(&tsconfig));
+    assert_eq!(first.unwrap().paths, second.unwrap().paths);
+    // Reutilizado, pero sigue registrando lo que leyó para el caché
+    assert_eq!(first_probes, second_probes);
+    assert!(second_probes.iter().any(|p| p.path == root.join("base.json")));
+
+    fs::write(root.join("base.json"), r#"{ "compilerOptions": { "paths": { "@lib/*": ["new/*", "other/*"] } } }"#).unwrap();
+    let reloaded = TsConfig::load(&tsconfig).unwrap();
+    assert_eq!(reloaded.paths, [("@lib/*".to_string(), vec!["new/*".to_string(), "other/*".to_string()])]);
+}
+
+// ---------------------------- Extensiones ----------------------------
+
+#[test]
+fn test_js_specifier_maps_to_ts_source() {
+    let root = canonical_temp_project("resolve_ext_js_to_ts", &[
+        ("main.ts", "import { a } from './a.js';\nimport { b } from './b.js';\nimport { c } from './c.jsx';"),
+        ("a.ts", "export const a = 1;"),
+        ("b.js", "export const b = 1;"),
+        ("c.tsx", "export const c = 1;"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "a.ts", ResolutionRule::Relative);
+    resolved(&imports[1], &root, "b.js", ResolutionRule::Relative);
+    resolved(&imports[2], &root, "c.tsx", ResolutionRule::Relative);
+}
+
+#[test]
+fn test_mjs_and_cjs_specifiers_map_to_mts_and_cts() {
+    let root = canonical_temp_project("resolve_ext_mts_cts", &[
+        ("main.mts", "import { a } from './a.mjs';\nimport { b } from './b.cjs';\nimport { c } from './c.mjs';"),
+        ("a.mts", "export const a = 1;"),
+        ("b.cts", "export const b = 1;"),
+        ("c.mjs", "export const c = 1;"),
+    ]);
+
+    let imports = imports_of(&root, "main.mts");
+    resolved(&imports[0], &root, "a.mts", ResolutionRule::Relative);
+    resolved(&imports[1], &root, "b.cts", ResolutionRule::Relative);
+    resolved(&imports[2], &root, "c.mjs", ResolutionRule::Relative);
+}
+
+#[test]
+fn test_declaration_files() {
+    let root = canonical_temp_project("resolve_ext_dts", &[
+        ("main.ts", "import { a } from './a';\nimport { b } from './b.js';\nimport { c } from './c.mjs';"),
+        ("a.d.ts", "export declare const a: number;"),
+        ("b.d.ts", "export declare const b: number;"),
+        ("c.d.mts", "export declare const c: number;"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "a.d.ts", ResolutionRule::Relative);
+    resolved(&imports[1], &root, "b.d.ts", ResolutionRule::Relative);
+    resolved(&imports[2], &root, "c.d.mts", ResolutionRule::Relative);
+}
+
+#[test]
+fn test_directory_index_variants() {
+    let root = canonical_temp_project("resolve_ext_index", &[
+        ("main.ts", "import { a } from './components';\nimport { b } from './legacy';"),
+        ("components/index.tsx", "export const a = 1;"),
+        ("legacy/index.js", "export const b = 1;"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "components/index.tsx", ResolutionRule::Relative);
+    resolved(&imports[1], &root, "legacy/index.js", ResolutionRule::Relative);
+}
+
+#[test]
+fn test_dotted_file_names_keep_their_suffix() {
+    let root = canonical_temp_project("resolve_ext_dotted", &[
+        ("main.ts", "import { UserService } from './user.service';"),
+        ("user.ts", "export const wrong = 1;"),
+        ("user.service.ts", "export class UserService {}"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "user.service.ts", ResolutionRule::Relative);
+}
+
+#[test]
+fn test_extension_rules_apply_to_paths_targets() {
+    let root = canonical_temp_project("resolve_ext_paths", &[
+        ("tsconfig.json", r#"{ "compilerOptions": { "moduleResolution": "bundler", "paths": { "@lib/*": ["lib/*"] } } }"#),
+        ("main.ts", "import { a } from '@lib/a.js';"),
+        ("lib/a.ts", "export const a = 1;"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "lib/a.ts", ResolutionRule::Paths { pattern: "@lib/*".to_string() });
+}
+
+// ---------------------------- Python ----------------------------
+
+#[test]
+fn test_python_resolution_rules() {
+    let root = canonical_temp_project("resolve_py_rules", &[
+        ("pkg/main.py", "from .helpers import a\nimport pkg.models"),
+        ("pkg/helpers.py", "def a():\n    pass"),
+        ("pkg/models.py", ""),
+    ]);
+
+    let imports = imports_of(&root, "pkg/main.py");
+    resolved(&imports[0], &root, "pkg/helpers.py", ResolutionRule::Relative);
+    resolved(&imports[1], &root, "pkg/models.py", ResolutionRule::ProjectRoot);
+}
+
+// ---------------------------- node_modules ----------------------------
+
+fn package(import: &ImportInfo) -> (&str, Option<&str>) {
+    let package = import.package.as_ref().expect("import sin paquete");
+    (package.name.as_str(), package.version.as_deref())
+}
+
+#[test]
+fn test_package_main_and_version() {
+    let root = canonical_temp_project("resolve_pkg_main", &[
+        ("main.ts", "import { chunk } from 'lodash';\nimport { a } from './a';"),
+        ("a.ts", "export const a = 1;"),
+        ("node_modules/lodash/package.json", r#"{ "name": "lodash", "version": "4.17.21", "main": "lodash.js" }"#),
+        ("node_modules/lodash/lodash.js", "exports.chunk = function () {};"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "node_modules/lodash/lodash.js", ResolutionRule::NodeModules);
+    assert_eq!(package(&imports[0]), ("lodash", Some("4.17.21")));
+    resolved(&imports[1], &root, "a.ts", ResolutionRule::Relative);
+    assert!(imports[1].package.is_none());
+}
+
+#[test]
+fn test_package_types_preferred_over_main() {
+    let root = canonical_temp_project("resolve_pkg_types", &[
+        ("main.ts", "import { z } from 'zod';"),
+        ("node_modules/zod/package.json", r#"{ "name": "zod", "main": "lib/index.js", "types": "lib/index.d.ts" }"#),
+        ("node_modules/zod/lib/index.js", ""),
+        ("node_modules/zod/lib/index.d.ts", "export declare const z: any;"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "node_modules/zod/lib/index.d.ts", ResolutionRule::NodeModules);
+    assert_eq!(package(&imports[0]), ("zod", None));
+}
+
+#[test]
+fn test_package_exports_conditions_and_patterns() {
+    let root = canonical_temp_project("resolve_pkg_exports", &[
+        ("main.ts", "import { a } from 'kit';\nimport { b } from 'kit/features/b';\nimport { c } from 'kit/private';"),
+        ("node_modules/kit/package.json", r#"{
+            "name": "kit",
+            "main": "legacy.js",
+            "exports": {
+                ".": { "require": "./dist/index.cjs", "import": "./dist/index.mjs" },
+                "./features/*": { "default": "./dist/features/*.js" }
+            }
+        }"#),
+        ("node_modules/kit/legacy.js", ""),
+        ("node_modules/kit/private.js", ""),
+        ("node_modules/kit/dist/index.mjs", ""),
+        ("node_modules/kit/dist/index.cjs", ""),
+        ("node_modules/kit/dist/features/b.js", ""),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "node_modules/kit/dist/index.mjs", ResolutionRule::NodeModules);
+    resolved(&imports[1], &root, "node_modules/kit/dist/features/b.js", ResolutionRule::NodeModules);
+    // Fuera de `exports` el paquete no expone nada, aunque el archivo exista
+    assert!(imports[2].path.is_none());
+}
+
+#[test]
+fn test_scoped_package_subpath() {
+    let root = canonical_temp_project("resolve_pkg_scoped", &[
+        ("main.ts", "import { Injectable } from '@nestjs/common/decorators';"),
+        ("node_modules/@nestjs/common/package.json", r#"{ "name": "@nestjs/common", "version": "10.3.0" }"#),
+        ("node_modules/@nestjs/common/decorators.ts", "export function Injectable() {}"),
+    ]);
+
+    let imports = imports_of(&root, "main.ts");
+    resolved(&imports[0], &root, "node_modules/@nestjs/common/decorators.ts", ResolutionRule::NodeModules);
+    assert_eq!(package(&imports[0]), ("@nestjs/common", Some("10.3.0")));
+}
+
+#[test]
+fn test_package_index_fallback_from_parent_node_modules() {
+    let root = canonical_temp_project("resolve_pkg_index", &[
+        ("packages/app/src/main.ts", "import { x } from 'shared';"),
+        ("node_modules/shared/index.ts", "export const x = 1;"),
+    ]);
+
+    let imports = imports_of(&root, "packages/app/src/main.ts");
+    resolved(&imports[0], &root, "node_modules/shared/index.ts", ResolutionRule::NodeModules);
+    // Sin package.json el nombre sale del especificador
+    assert_eq!(package(&imports[0]), ("shared", None));
+}
@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter_test::Analyzer;
+use tree_sitter_test::output::{DirectorySink, MemorySink, OutputSink};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tree_sitter_test_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// ---------------------------- Analyzer ----------------------------
+
+#[test]
+fn test_analyzer_returns_typed_result() {
+    let analyzer = Analyzer::new(vec![]);
+    let result = analyzer.analyze_source("def foo():\n    pass", Path::new("a.py")).unwrap();
+
+    assert_eq!(result.functions.len(), 1);
+    assert_eq!(result.functions[0].name, "foo");
+}
+
+#[test]
+fn test_analyzer_reads_file() {
+    let dir = temp_dir("analyzer_reads_file");
+    let file = dir.join("mod.ts");
+    fs::write(&file, "function hola(): void {}").unwrap();
+
+    let result = Analyzer::new(vec![dir.clone()]).analyze_file(&file).unwrap();
+
+    assert_eq!(result.functions[0].name, "hola");
+}
+
+// ---------------------------- Sinks ----------------------------
+
+#[test]
+fn test_memory_sink_keeps_results() {
+    let analyzer = Analyzer::new(vec![]);
+    let mut sink = MemorySink::new();
+
+    let path = Path::new("a.py");
+    let result = analyzer.analyze_source("import os", path).unwrap();
+    sink.write(path, &result).unwrap();
+
+    assert_eq!(sink.results.len(), 1);
+    assert_eq!(sink.get(path).unwrap().imports[0].name, "os");
+}
+
+#[test]
+fn test_directory_sink_avoids_stem_collisions() {
+    let out = temp_dir("directory_sink_collisions");
+    let analyzer = Analyzer::new(vec![]);
+    let mut sink = DirectorySink::new(&out).with_base("project");
+
+    let first = Path::new("project/a/utils.py");
+    let second = Path::new("project/b/utils.py");
+    sink.write(first, &analyzer.analyze_source("def first():\n    pass", first).unwrap()).unwrap();
+    sink.write(second, &analyzer.analyze_source("def second():\n    pass", second).unwrap()).unwrap();
+
+    let first_json = fs::read_to_string(out.join("a/utils.py.json")).unwrap();
+    let second_json = fs::read_to_string(out.join("b/utils.py.json")).unwrap();
+    assert!(first_json.contains("\"first\""));
+    assert!(second_json.contains("\"second\""));
+    assert_eq!(sink.written().count(), 2);
+}
+
+#[test]
+fn test_directory_sink_stays_inside_output_dir() {
+    let sink = DirectorySink::new("out");
+
+    assert_eq!(sink.output_path(Path::new("/abs/x.ts")), PathBuf::from("out/abs/x.ts.json"));
+    assert_eq!(sink.output_path(Path::new("../x.ts")), PathBuf::from("out/__parent__/x.ts.json"));
+}
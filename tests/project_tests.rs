@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::Path;
+use tree_sitter_test::error::AnalysisError;
+use tree_sitter_test::{Analyzer, ProjectOptions};
+
+mod common;
+use common::temp_project;
+
+// ---------------------------- Walking ----------------------------
+
+#[test]
+fn test_analyzes_every_supported_file() {
+    let root = temp_project("project_all_files", &[
+        ("main.py", "def main():\n    pass"),
+        ("pkg/utils.py", "def helper():\n    pass"),
+        ("web/app.ts", "function start(): void {}"),
+    ]);
+
+    let project = Analyzer::new(vec![root.clone()]).analyze_project(&root, &ProjectOptions::new()).unwrap();
+
+    let keys: Vec<&Path> = project.files.keys().map(|p| p.as_path()).collect();
+    assert_eq!(keys, vec![Path::new("main.py"), Path::new("pkg/utils.py"), Path::new("web/app.ts")]);
+    assert_eq!(project.files[Path::new("pkg/utils.py")].functions[0].name, "helper");
+    assert!(project.errors.is_empty());
+}
+
+#[test]
+fn test_skips_unsupported_extensions() {
+    let root = temp_project("project_unsupported", &[
+        ("main.py", "import os"),
+        ("README.md", "# hola"),
+        ("script.rb", "puts 'hola'"),
+    ]);
+
+    let project = Analyzer::new(vec![]).analyze_project(&root, &ProjectOptions::new()).unwrap();
+
+    assert_eq!(project.files.len(), 1);
+    assert!(project.files.contains_key(Path::new("main.py")));
+    assert!(project.errors.is_empty());
+}
+
+#[test]
+fn test_respects_gitignore() {
+    let root = temp_project("project_gitignore", &[
+        (".gitignore", "build/\n*.generated.ts\n"),
+        ("src/app.ts", "function a(): void {}"),
+        ("src/api.generated.ts", "function b(): void {}"),
+        ("build/out.py", "def c():\n    pass"),
+    ]);
+
+    let project = Analyzer::new(vec![]).analyze_project(&root, &ProjectOptions::new()).unwrap();
+    assert_eq!(project.files.keys().collect::<Vec<_>>(), vec![Path::new("src/app.ts")]);
+
+    let all = Analyzer::new(vec![]).analyze_project(&root, &ProjectOptions::new().respect_gitignore(false)).unwrap();
+    assert_eq!(all.files.len(), 3);
+}
+
+#[test]
+fn test_include_and_exclude_globs() {
+    let root = temp_project("project_globs", &[
+        ("src/a.py", "import os"),
+        ("src/tests/test_a.py", "import os"),
+        ("scripts/tool.py", "import os"),
+    ]);
+
+    let options = ProjectOptions::new().include("src/**").exclude("**/tests/**");
+    let project = Analyzer::new(vec![]).analyze_project(&root, &options).unwrap();
+
+    assert_eq!(project.files.keys().collect::<Vec<_>>(), vec![Path::new("src/a.py")]);
+}
+
+#[test]
+fn test_unreadable_file_is_reported_not_fatal() {
+    let root = temp_project("project_bad_file", &[("good.py", "import os")]);
+    fs::write(root.join("bad.py"), [0xff, 0xfe, 0x00]).unwrap();
+
+    let project = Analyzer::new(vec![]).analyze_project(&root, &ProjectOptions::new()).unwrap();
+
+    assert!(project.files.contains_key(Path::new("good.py")));
+    assert!(project.errors.contains_key(Path::new("bad.py")));
+}
+
+// ---------------------------- Errors ----------------------------
+
+#[test]
+fn test_invalid_glob_is_an_error() {
+    let root = temp_project("project_invalid_glob", &[("a.py", "")]);
+
+    let result = Analyzer::new(vec![]).analyze_project(&root, &ProjectOptions::new().include("src/[a"));
+
+    assert!(matches!(result, Err(AnalysisError::InvalidPattern { .. })));
+}
+
+#[test]
+fn test_missing_root_is_an_error() {
+    let result = Analyzer::new(vec![]).analyze_project(Path::new("does/not/exist"), &ProjectOptions::new());
+
+    assert!(matches!(result, Err(AnalysisError::Io { .. })));
+}
+
+// ---------------------------- Workers ----------------------------
+
+#[test]
+fn test_parallel_output_matches_sequential() {
+    let mut files = vec![];
+    for n in 0..40 {
+        files.push((format!("pkg/m{}.py", n), format!("def f{}(a, b: int = {}):\n    return g(a)", n, n)));
+        files.push((format!("web/m{}.ts", n), format!("function f{}(a: number): number {{ return g(a); }}", n)));
+    }
+    let refs: Vec<(&str, &str)> = files.iter().map(|(p, c)| (p.as_str(), c.as_str())).collect();
+    let root = temp_project("project_parallel", &refs);
+
+    let analyzer = Analyzer::new(vec![root.clone()]);
+    let sequential = analyzer.analyze_project(&root, &ProjectOptions::new().workers(1)).unwrap();
+    let parallel = analyzer.analyze_project(&root, &ProjectOptions::new().workers(8)).unwrap();
+
+    assert_eq!(parallel.files.len(), 80);
+    assert_eq!(
+        serde_json::to_string(&sequential).unwrap(),
+        serde_json::to_string(&parallel).unwrap()
+    );
+}
+
+#[test]
+fn test_workers_is_at_least_one() {
+    assert_eq!(ProjectOptions::new().workers(0).worker_count(), 1);
+}
@@ -1,6 +1,12 @@
 use std::path::PathBuf;
+use tree_sitter_test::models::class_info::{ClassInfo, FieldInfo, FieldKind};
+use tree_sitter_test::models::documentation::DocStyle;
+use tree_sitter_test::models::parameter_info::ParameterKind;
 use tree_sitter_test::parser::parse_file;
 
+mod common;
+use common::names;
+
 fn dummy_path() -> std::path::PathBuf {
     PathBuf::from("test_file.py")
 }
@@ -14,7 +20,7 @@ fn dummy_roots() -> Vec<PathBuf> {
 #[test]
 fn test_plain_import() {
     let source = "import math";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 1);
     assert_eq!(result.imports[0].name, "math");
@@ -24,39 +30,75 @@ fn test_plain_import() {
 #[test]
 fn test_from_import_single() {
     let source = "from math import sqrt";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 1);
     assert_eq!(result.imports[0].name, "math");
-    assert_eq!(result.imports[0].imported_names, vec!["sqrt"]);
+    assert_eq!(names(&result.imports[0]), vec!["sqrt"]);
 }
 
 #[test]
 fn test_from_import_multiple() {
     let source = "from math import add, subtract";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 1);
     assert_eq!(result.imports[0].name, "math");
-    assert_eq!(result.imports[0].imported_names, vec!["add", "subtract"]);
+    assert_eq!(names(&result.imports[0]), vec!["add", "subtract"]);
 }
 
 #[test]
 fn test_multiple_imports() {
     let source = "import os\nimport sys";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 2);
     assert_eq!(result.imports[0].name, "os");
     assert_eq!(result.imports[1].name, "sys");
 }
 
+#[test]
+fn test_import_with_alias() {
+    let source = "import numpy as np";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 1);
+    assert_eq!(result.imports[0].name, "numpy");
+    assert_eq!(result.imports[0].alias.as_deref(), Some("np"));
+    assert_eq!(result.imports[0].local_name(), "np");
+}
+
+#[test]
+fn test_import_several_modules() {
+    let source = "import sys, os.path as osp, json";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let imports: Vec<(&str, Option<&str>)> = result.imports.iter()
+        .map(|i| (i.name.as_str(), i.alias.as_deref()))
+        .collect();
+    assert_eq!(imports, vec![("sys", None), ("os.path", Some("osp")), ("json", None)]);
+    assert!(result.imports.iter().all(|i| i.line == 1));
+}
+
+#[test]
+fn test_from_import_with_alias() {
+    let source = "from math import sqrt as root, pi";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(names(&result.imports[0]), vec!["sqrt", "pi"]);
+    let imported = &result.imports[0].imported_names;
+    assert_eq!(imported[0].alias.as_deref(), Some("root"));
+    assert_eq!(imported[1].alias, None);
+    assert_eq!(result.imports[0].imported("root").map(|n| n.name.as_str()), Some("sqrt"));
+    assert!(result.imports[0].imported("sqrt").is_none());
+}
+
 // ---------------------------- Functions ----------------------------
 
 #[test]
 fn test_simple_function() {
     let source = "def greet():\n    pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     assert_eq!(result.functions[0].name, "greet");
@@ -67,7 +109,7 @@ fn test_simple_function() {
 #[test]
 fn test_function_with_typed_params() {
     let source = "def add(a: int, b: int) -> int:\n    pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     let func = &result.functions[0];
@@ -83,7 +125,7 @@ fn test_function_with_typed_params() {
 #[test]
 fn test_function_with_default_param() {
     let source = "def greet(name, greeting=\"hello\"):\n    pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let func = &result.functions[0];
     assert_eq!(func.parameters.len(), 2);
@@ -94,7 +136,7 @@ fn test_function_with_default_param() {
 #[test]
 fn test_function_with_typed_default_param() {
     let source = "def greet(name: str, greeting: str = \"hello\") -> str:\n    pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let func = &result.functions[0];
     assert_eq!(func.parameters.len(), 2);
@@ -104,6 +146,43 @@ fn test_function_with_typed_default_param() {
     assert_eq!(p.default_value.as_deref(), Some("\"hello\""));
 }
 
+#[test]
+fn test_parameter_kinds() {
+    let source = "def f(a, b=1, /, c: int = 2, *args: int, d, e: str = 'x', **kwargs: dict):\n    pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let params: Vec<(&str, ParameterKind)> = result.functions[0].parameters.iter().map(|p| (p.name.as_str(), p.kind)).collect();
+    assert_eq!(params, vec![
+        ("a", ParameterKind::PositionalOnly),
+        ("b", ParameterKind::PositionalOnly),
+        ("c", ParameterKind::PositionalOrKeyword),
+        ("args", ParameterKind::VarPositional),
+        ("d", ParameterKind::KeywordOnly),
+        ("e", ParameterKind::KeywordOnly),
+        ("kwargs", ParameterKind::VarKeyword),
+    ]);
+    assert_eq!(result.functions[0].parameters[3].param_type.as_deref(), Some("int"));
+    assert_eq!(result.functions[0].parameters[6].param_type.as_deref(), Some("dict"));
+}
+
+#[test]
+fn test_bare_star_and_untyped_splats() {
+    let source = "def f(self, *, key=None, **options):\n    pass\ndef g(*items):\n    pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let kinds: Vec<(&str, ParameterKind)> = result.functions[0].parameters.iter().map(|p| (p.name.as_str(), p.kind)).collect();
+    assert_eq!(kinds, vec![
+        ("self", ParameterKind::PositionalOrKeyword),
+        ("key", ParameterKind::KeywordOnly),
+        ("options", ParameterKind::VarKeyword),
+    ]);
+    assert_eq!(result.functions[0].parameters[1].default_value.as_deref(), Some("None"));
+
+    let items = &result.functions[1].parameters[0];
+    assert_eq!((items.name.as_str(), items.kind), ("items", ParameterKind::VarPositional));
+    assert!(items.param_type.is_none());
+}
+
 // ---------------------------- Function Calls ----------------------------
 
 #[test]
@@ -112,7 +191,7 @@ fn test_bare_call_resolved_via_from_import() {
 from math_utils import add
 def compute(x, y):
     return add(x, y)";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
@@ -126,7 +205,7 @@ fn test_dotted_call_resolved_via_plain_import() {
 import math_utils
 def compute(x, y):
     return math_utils.subtract(x, y)";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
@@ -137,7 +216,7 @@ def compute(x, y):
 #[test]
 fn test_unresolved_call_has_no_import() {
     let source = "def compute():\n    print('hello')";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
@@ -153,7 +232,7 @@ def compute(x, y):
     a = add(x, y)
     b = subtract(x, y)
     return a + b";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 2);
@@ -161,6 +240,59 @@ def compute(x, y):
     assert!(calls.iter().any(|c| c.name == "subtract" && c.import_name.as_deref() == Some("math_utils")));
 }
 
+#[test]
+fn test_call_through_module_alias() {
+    let source = "\
+import numpy as np
+def build():
+    return np.array([1, 2])";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    assert_eq!(calls[0].name, "array");
+    assert_eq!(calls[0].import_name.as_deref(), Some("numpy"));
+    assert!(calls[0].object_name.is_none());
+}
+
+#[test]
+fn test_call_through_imported_name_alias() {
+    let source = "\
+from math import sqrt as root
+def hypot(x, y):
+    return root(x * x + y * y)";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    assert_eq!(calls[0].name, "sqrt");
+    assert_eq!(calls[0].import_name.as_deref(), Some("math"));
+}
+
+#[test]
+fn test_aliased_module_name_is_not_bound() {
+    let source = "\
+import numpy as np
+def build():
+    return numpy.array([1, 2])";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    assert!(calls[0].import_name.is_none());
+    assert_eq!(calls[0].object_name.as_deref(), Some("numpy"));
+}
+
+#[test]
+fn test_call_through_dotted_module_import() {
+    let source = "\
+import os.path
+def join(a, b):
+    return os.path.join(a, b)";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    assert_eq!(calls[0].name, "join");
+    assert_eq!(calls[0].import_name.as_deref(), Some("os.path"));
+}
+
 // ---------------------------- Classes ----------------------------
 
 #[test]
@@ -169,7 +301,7 @@ fn test_simple_class() {
 class MyClass:
     def my_method(self):
         pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.classes.len(), 1);
     assert_eq!(result.classes[0].name, "MyClass");
@@ -183,7 +315,7 @@ fn test_class_method_with_typed_params() {
 class Calculator:
     def multiply(self, a: int, b: int) -> int:
         pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let method = &result.classes[0].methods[0];
     assert_eq!(method.name, "multiply");
@@ -200,7 +332,7 @@ from math_utils import subtract
 class MyClass:
     def my_method(self, x: int, y: int):
         return subtract(x, y)";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.classes[0].methods[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
@@ -214,18 +346,533 @@ fn test_class_not_added_to_functions() {
 class MyClass:
     def my_method(self):
         pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert!(result.functions.is_empty());
     assert_eq!(result.classes.len(), 1);
 }
 
+// ---------------------------- Inheritance & Decorators ----------------------------
+
+#[test]
+fn test_class_bases_resolved_through_imports() {
+    let source = "\
+from django.db import models
+from typing import Generic, TypeVar
+import abc as a
+from .base import Base as B
+
+class User(models.Model, B, Generic[T], a.ABC, Local):
+    pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let bases: Vec<(&str, &str, Option<&str>)> = result.classes[0].bases.iter()
+        .map(|b| (b.name.as_str(), b.expression.as_str(), b.import_name.as_deref()))
+        .collect();
+    assert_eq!(bases, vec![
+        ("Model", "models.Model", Some("models")),
+        ("Base", "B", Some(".base")),
+        ("Generic", "Generic[T]", Some("typing")),
+        ("ABC", "a.ABC", Some("abc")),
+        ("Local", "Local", None),
+    ]);
+}
+
+#[test]
+fn test_class_keyword_arguments() {
+    let source = "\
+from abc import ABCMeta
+class Plugin(Base, metaclass=ABCMeta, registry='plugins'):
+    pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let class = &result.classes[0];
+    assert_eq!(class.bases.len(), 1);
+    let keywords: Vec<(&str, &str)> = class.keywords.iter().map(|k| (k.name.as_str(), k.value.as_str())).collect();
+    assert_eq!(keywords, vec![("metaclass", "ABCMeta"), ("registry", "'plugins'")]);
+}
+
+#[test]
+fn test_class_decorators() {
+    let source = "\
+import attr
+from dataclasses import dataclass
+
+@dataclass(frozen=True, slots=True)
+@attr.s
+class Point:
+    pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let decorators = &result.classes[0].decorators;
+    assert_eq!(decorators.len(), 2);
+    assert_eq!(decorators[0].name, "dataclass");
+    assert_eq!(decorators[0].import_name.as_deref(), Some("dataclasses"));
+    assert_eq!(decorators[0].arguments, vec!["frozen=True", "slots=True"]);
+    assert_eq!(decorators[0].line, 4);
+    assert_eq!(decorators[1].name, "s");
+    assert_eq!(decorators[1].expression, "attr.s");
+    assert_eq!(decorators[1].import_name.as_deref(), Some("attr"));
+    assert!(decorators[1].arguments.is_empty());
+    // El span de la clase sigue empezando en `class`
+    assert_eq!(result.classes[0].line, 6);
+}
+
+#[test]
+fn test_function_and_method_decorators() {
+    let source = "\
+from flask import Flask
+app = Flask(__name__)
+
+@app.route('/users', methods=['GET'])
+def users():
+    pass
+
+class Shape:
+    @property
+    def area(self):
+        return 0
+
+    @staticmethod
+    def unit():
+        pass
+
+def plain():
+    pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let route = &result.functions[0].decorators[0];
+    assert_eq!(route.expression, "app.route");
+    assert_eq!(route.name, "route");
+    assert!(route.import_name.is_none());
+    assert_eq!(route.arguments, vec!["'/users'", "methods=['GET']"]);
+
+    let methods = &result.classes[0].methods;
+    assert_eq!(methods[0].decorators[0].name, "property");
+    assert_eq!(methods[1].decorators[0].name, "staticmethod");
+    assert!(result.functions[1].decorators.is_empty());
+}
+
+// ---------------------------- Nesting ----------------------------
+
+#[test]
+fn test_nested_function_is_not_top_level() {
+    let source = "\
+def outer(x):
+    def helper(y):
+        def deepest():
+            pass
+        return y
+    return helper(x)
+
+def api():
+    pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let names: Vec<&str> = result.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["outer", "api"]);
+
+    let helper = &result.functions[0].functions[0];
+    assert_eq!(helper.name, "helper");
+    assert_eq!(helper.qualified_name, "outer.<locals>.helper");
+    assert_eq!(helper.parent.as_deref(), Some("outer"));
+    assert_eq!(helper.functions[0].qualified_name, "outer.<locals>.helper.<locals>.deepest");
+    assert!(result.functions[0].parent.is_none());
+    assert_eq!(result.functions[0].qualified_name, "outer");
+}
+
+#[test]
+fn test_nested_classes() {
+    let source = "\
+class Outer:
+    class Meta:
+        ordering = ['name']
+
+        def describe(self):
+            pass
+
+    def method(self):
+        class Local:
+            pass
+        def closure():
+            pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.classes.len(), 1);
+    let outer = &result.classes[0];
+    assert_eq!(outer.methods.len(), 1);
+
+    let meta = &outer.classes[0];
+    assert_eq!(meta.qualified_name, "Outer.Meta");
+    assert_eq!(meta.parent.as_deref(), Some("Outer"));
+    assert_eq!(meta.methods[0].qualified_name, "Outer.Meta.describe");
+    assert_eq!(meta.methods[0].parent.as_deref(), Some("Outer.Meta"));
+    assert_eq!(meta.fields[0].name, "ordering");
+
+    let method = &outer.methods[0];
+    assert_eq!(method.qualified_name, "Outer.method");
+    assert_eq!(method.classes[0].qualified_name, "Outer.method.<locals>.Local");
+    assert_eq!(method.functions[0].qualified_name, "Outer.method.<locals>.closure");
+    assert_eq!(method.functions[0].parent.as_deref(), Some("Outer.method"));
+}
+
+#[test]
+fn test_nested_calls_belong_to_their_own_function() {
+    let source = "\
+def outer():
+    def inner():
+        helper()
+    class Local:
+        def method(self):
+            other()
+    callback = lambda: ignored()
+    inner()";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let outer = &result.functions[0];
+    let calls: Vec<&str> = outer.function_calls.as_ref().unwrap().iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(calls, vec!["inner"]);
+
+    let inner_calls: Vec<&str> = outer.functions[0].function_calls.as_ref().unwrap().iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(inner_calls, vec!["helper"]);
+    assert_eq!(outer.classes[0].methods[0].function_calls.as_ref().unwrap()[0].name, "other");
+}
+
+// ---------------------------- Fields ----------------------------
+
+fn field<'a>(class: &'a ClassInfo, name: &str, kind: FieldKind) -> &'a FieldInfo {
+    class.fields.iter().find(|f| f.name == name && f.kind == kind)
+        .unwrap_or_else(|| panic!("field {} not found", name))
+}
+
+#[test]
+fn test_class_level_fields() {
+    let source = "\
+class Config:
+    debug: bool = False
+    name: str
+    retries = timeout = 3
+    def run(self):
+        pass";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let class = &result.classes[0];
+    let names: Vec<&str> = class.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["debug", "name", "retries", "timeout"]);
+    assert!(class.fields.iter().all(|f| f.kind == FieldKind::Class && f.declared_in.is_none()));
+
+    let debug = field(class, "debug", FieldKind::Class);
+    assert_eq!(debug.annotation.as_deref(), Some("bool"));
+    assert_eq!(debug.value.as_deref(), Some("False"));
+    assert_eq!(debug.line, 2);
+    assert!(field(class, "name", FieldKind::Class).value.is_none());
+    assert_eq!(field(class, "timeout", FieldKind::Class).value.as_deref(), Some("3"));
+}
+
+#[test]
+fn test_instance_fields_from_methods() {
+    let source = "\
+class User:
+    def __init__(self, name: str):
+        self.name: str = name
+        if name:
+            self.active = True
+        self.name = name.strip()
+        helper = 1
+
+    def rename(this, name):
+        this.previous = this.name
+        this.count += 1
+
+        def inner(self):
+            self.hidden = 1";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let class = &result.classes[0];
+    let names: Vec<&str> = class.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["name", "active", "previous"]);
+
+    let name = field(class, "name", FieldKind::Instance);
+    assert_eq!(name.annotation.as_deref(), Some("str"));
+    assert_eq!(name.value.as_deref(), Some("name"));
+    assert_eq!(name.declared_in.as_deref(), Some("__init__"));
+    assert_eq!(name.line, 3);
+    assert_eq!(field(class, "previous", FieldKind::Instance).declared_in.as_deref(), Some("rename"));
+}
+
+#[test]
+fn test_classmethod_and_staticmethod_fields() {
+    let source = "\
+class Registry:
+    items = []
+
+    @classmethod
+    def reset(cls):
+        cls.items = []
+        cls.version = 2
+
+    @staticmethod
+    def build(self):
+        self.ignored = 1";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let class = &result.classes[0];
+    assert_eq!(class.fields.len(), 2);
+    assert!(field(class, "items", FieldKind::Class).declared_in.is_none());
+    assert_eq!(field(class, "version", FieldKind::Class).declared_in.as_deref(), Some("reset"));
+}
+
+// ---------------------------- Docstrings ----------------------------
+
+#[test]
+fn test_module_class_and_function_docstrings() {
+    let source = "\
+\"\"\"Utilidades de red.
+
+Más detalle.
+\"\"\"
+import os
+
+class Client:
+    '''Cliente HTTP.'''
+
+    def get(self, url):
+        r\"\"\"
+        Hace un GET.
+
+            Ejemplo indentado.
+        \"\"\"
+        return url
+
+def undocumented():
+    x = \"no es docstring\"
+
+def formatted():
+    f\"no es docstring\"";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let module = result.documentation.as_ref().unwrap();
+    assert_eq!(module.text, "Utilidades de red.\n\nMás detalle.");
+    assert_eq!(module.summary.as_deref(), Some("Utilidades de red."));
+    assert_eq!(module.style, DocStyle::Plain);
+
+    let class = &result.classes[0];
+    assert_eq!(class.documentation.as_ref().unwrap().text, "Cliente HTTP.");
+    assert_eq!(class.methods[0].documentation.as_ref().unwrap().text, "Hace un GET.\n\n    Ejemplo indentado.");
+    assert!(result.functions[0].documentation.is_none());
+    assert!(result.functions[1].documentation.is_none());
+}
+
+#[test]
+fn test_google_docstring() {
+    let source = "\
+def fetch(url, retries=3, *args, **kwargs):
+    \"\"\"Descarga una URL.
+
+    Args:
+        url (str): Dirección a descargar.
+        retries (int, optional): Reintentos antes
+            de fallar.
+        *args: Posicionales extra.
+        **kwargs: Opciones del cliente.
+
+    Returns:
+        bytes: El cuerpo de la respuesta.
+
+    Raises:
+        TimeoutError: Si no responde.
+        ValueError: Si la URL es inválida.
+    \"\"\"";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let func = &result.functions[0];
+    let doc = func.documentation.as_ref().unwrap();
+    assert_eq!(doc.style, DocStyle::Google);
+    assert_eq!(doc.summary.as_deref(), Some("Descarga una URL."));
+    assert_eq!(doc.params.len(), 4);
+    assert_eq!(doc.params[1].param_type.as_deref(), Some("int, optional"));
+    assert_eq!(doc.params[1].description, "Reintentos antes de fallar.");
+
+    let returns = doc.returns.as_ref().unwrap();
+    assert_eq!(returns.return_type.as_deref(), Some("bytes"));
+    assert_eq!(returns.description, "El cuerpo de la respuesta.");
+    let raises: Vec<&str> = doc.raises.iter().map(|r| r.exception.as_str()).collect();
+    assert_eq!(raises, vec!["TimeoutError", "ValueError"]);
+
+    let descriptions: Vec<Option<&str>> = func.parameters.iter().map(|p| p.description.as_deref()).collect();
+    assert_eq!(descriptions, vec![
+        Some("Dirección a descargar."),
+        Some("Reintentos antes de fallar."),
+        Some("Posicionales extra."),
+        Some("Opciones del cliente."),
+    ]);
+}
+
+#[test]
+fn test_numpy_docstring() {
+    let source = "\
+def solve(a, b, tol=1e-6):
+    \"\"\"
+    Resuelve el sistema.
+
+    Parameters
+    ----------
+    a, b : ndarray
+        Matrices del sistema.
+    tol : float
+        Tolerancia.
+
+    Returns
+    -------
+    x : ndarray
+        La solución.
+
+    Raises
+    ------
+    LinAlgError
+        Si la matriz es singular.
+    \"\"\"";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let func = &result.functions[0];
+    let doc = func.documentation.as_ref().unwrap();
+    assert_eq!(doc.style, DocStyle::Numpy);
+    assert_eq!(doc.summary.as_deref(), Some("Resuelve el sistema."));
+    let params: Vec<(&str, Option<&str>)> = doc.params.iter().map(|p| (p.name.as_str(), p.param_type.as_deref())).collect();
+    assert_eq!(params, vec![("a", Some("ndarray")), ("b", Some("ndarray")), ("tol", Some("float"))]);
+
+    let returns = doc.returns.as_ref().unwrap();
+    assert_eq!(returns.return_type.as_deref(), Some("ndarray"));
+    assert_eq!(returns.description, "La solución.");
+    assert_eq!(doc.raises[0].exception, "LinAlgError");
+    assert_eq!(doc.raises[0].description, "Si la matriz es singular.");
+
+    assert_eq!(func.parameters[1].description.as_deref(), Some("Matrices del sistema."));
+    assert_eq!(func.parameters[2].description.as_deref(), Some("Tolerancia."));
+}
+
+#[test]
+fn test_rest_docstring() {
+    let source = "\
+def connect(host, port):
+    \"\"\"Abre una conexión.
+
+    :param host: Nombre del servidor.
+    :type host: str
+    :param int port: Puerto, por defecto
+        el 80.
+    :returns: La conexión abierta.
+    :rtype: Connection
+    :raises ConnectionError: Si falla.
+    \"\"\"";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let func = &result.functions[0];
+    let doc = func.documentation.as_ref().unwrap();
+    assert_eq!(doc.style, DocStyle::Rest);
+    assert_eq!(doc.summary.as_deref(), Some("Abre una conexión."));
+    assert_eq!(doc.params[0].param_type.as_deref(), Some("str"));
+    assert_eq!(doc.params[1].param_type.as_deref(), Some("int"));
+    assert_eq!(doc.params[1].description, "Puerto, por defecto el 80.");
+
+    let returns = doc.returns.as_ref().unwrap();
+    assert_eq!(returns.return_type.as_deref(), Some("Connection"));
+    assert_eq!(returns.description, "La conexión abierta.");
+    assert_eq!(doc.raises[0].exception, "ConnectionError");
+
+    assert_eq!(func.parameters[0].description.as_deref(), Some("Nombre del servidor."));
+}
+
+#[test]
+fn test_class_docstring_documents_init_parameters() {
+    let source = "\
+class Point:
+    \"\"\"Un punto.
+
+    Args:
+        x: Abscisa.
+        y: Ordenada.
+    \"\"\"
+
+    def __init__(self, x, y):
+        \"\"\"Crea el punto.
+
+        Args:
+            y: Ordenada propia.
+        \"\"\"";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let init = &result.classes[0].methods[0];
+    let descriptions: Vec<Option<&str>> = init.parameters.iter().map(|p| p.description.as_deref()).collect();
+    assert_eq!(descriptions, vec![None, Some("Abscisa."), Some("Ordenada propia.")]);
+}
+
+// ---------------------------- Async & Generators ----------------------------
+
+#[test]
+fn test_async_and_generator_flags() {
+    let source = "\
+async def fetch():
+    pass
+
+def numbers():
+    x = yield 1
+    yield from range(x)
+
+async def stream():
+    yield 1
+
+def plain():
+    squares = (i * i for i in range(3))
+    def inner():
+        yield 1
+    return lambda: (yield)";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let flags: Vec<(&str, bool, bool)> = result.functions.iter()
+        .map(|f| (f.name.as_str(), f.is_async, f.is_generator))
+        .collect();
+    assert_eq!(flags, vec![
+        ("fetch", true, false),
+        ("numbers", false, true),
+        ("stream", true, true),
+        ("plain", false, false),
+    ]);
+    assert!(result.functions[3].functions[0].is_generator);
+}
+
+#[test]
+fn test_awaited_calls() {
+    let source = "\
+import asyncio
+
+async def main(client):
+    await asyncio.sleep(1)
+    task = asyncio.create_task(work())
+    data = await (client.get(url))
+    await gather(first(), second())";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls: Vec<(&str, bool)> = result.functions[0].function_calls.as_ref().unwrap().iter()
+        .map(|c| (c.name.as_str(), c.awaited))
+        .collect();
+    assert_eq!(calls, vec![
+        ("sleep", true),
+        ("create_task", false),
+        ("work", false),
+        ("get", true),
+        ("gather", true),
+        ("first", false),
+        ("second", false),
+    ]);
+}
+
 // ---------------------------- Line Numbers ----------------------------
 
 #[test]
 fn test_function_line_number() {
     let source = "def foo():\n    pass\n\ndef bar():\n    pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions[0].line, 1);
     assert_eq!(result.functions[1].line, 4);
@@ -237,7 +884,7 @@ fn test_class_and_method_line_number() {
 class MyClass:
     def my_method(self):
         pass";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.classes[0].line, 1);
     assert_eq!(result.classes[0].methods[0].line, 2);
@@ -249,8 +896,137 @@ fn test_function_call_line_number() {
 from math_utils import add
 def compute(x, y):
     return add(x, y)";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls[0].line, 3);
-}
\ No newline at end of file
+}
+// ---------------------------- Spans ----------------------------
+
+#[test]
+fn test_class_and_function_spans() {
+    let source = "\
+import os
+
+class Shape:
+    def area(self, scale: int = 1):
+        return 0";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let import = &result.imports[0].span;
+    assert_eq!((import.start_line, import.start_column, import.end_column), (1, 0, 9));
+    assert_eq!((import.start_byte, import.end_byte), (0, 9));
+
+    let class = &result.classes[0].span;
+    assert_eq!((class.start_line, class.end_line), (3, 5));
+    assert_eq!(class.end_byte, source.len());
+
+    let method = &result.classes[0].methods[0];
+    assert_eq!((method.span.start_line, method.span.start_column), (4, 4));
+    assert_eq!(method.span.end_line, 5);
+
+    let param = &method.parameters[1].span;
+    assert_eq!(&source[param.start_byte..param.end_byte], "scale: int = 1");
+    assert_eq!((param.start_line, param.start_column), (4, 19));
+}
+
+#[test]
+fn test_local_variable_span() {
+    let source = "\
+def build():
+    result = make()";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let span = &result.functions[0].local_variables[0].span;
+    assert_eq!(&source[span.start_byte..span.end_byte], "result = make()");
+    assert_eq!((span.start_line, span.start_column, span.end_column), (2, 4, 19));
+}
+
+// ---------------------------- Call Sites ----------------------------
+
+fn call_positions(result: &tree_sitter_test::models::analysis_result::AnalysisResult) -> Vec<(String, usize, usize)> {
+    let mut calls: Vec<_> = result.functions[0].function_calls.as_ref().unwrap().iter()
+        .map(|c| (c.name.clone(), c.line, c.span.start_column))
+        .collect();
+    calls.sort_by_key(|(_, line, column)| (*line, *column));
+    calls
+}
+
+#[test]
+fn test_each_call_reports_its_own_line() {
+    let source = "\
+def run():
+    setup()
+    execute()
+    teardown()";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(call_positions(&result), vec![
+        ("setup".to_string(), 2, 4),
+        ("execute".to_string(), 3, 4),
+        ("teardown".to_string(), 4, 4),
+    ]);
+}
+
+#[test]
+fn test_calls_in_nested_blocks() {
+    let source = "\
+def run(items):
+    if items:
+        for item in items:
+            with lock():
+                try:
+                    handle(item)
+                except ValueError:
+                    report(item)
+    return done()";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(call_positions(&result), vec![
+        ("lock".to_string(), 4, 17),
+        ("handle".to_string(), 6, 20),
+        ("report".to_string(), 8, 20),
+        ("done".to_string(), 9, 11),
+    ]);
+}
+
+#[test]
+fn test_call_with_multiline_arguments() {
+    let source = "\
+def run():
+    total = compute(
+        first(),
+        second(),
+    )";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(call_positions(&result), vec![
+        ("compute".to_string(), 2, 12),
+        ("first".to_string(), 3, 8),
+        ("second".to_string(), 4, 8),
+    ]);
+
+    let compute = result.functions[0].function_calls.as_ref().unwrap().iter().find(|c| c.name == "compute").unwrap();
+    assert_eq!((compute.span.end_line, compute.span.end_column), (5, 5));
+}
+
+#[test]
+fn test_chained_calls() {
+    let source = "\
+import requests
+def fetch(url):
+    return requests.get(url).json()";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    assert_eq!(calls.len(), 2);
+
+    let json = calls.iter().find(|c| c.name == "json").unwrap();
+    assert_eq!(json.object_name.as_deref(), Some("requests.get(url)"));
+    assert!(json.import_name.is_none());
+
+    let get = calls.iter().find(|c| c.name == "get").unwrap();
+    assert_eq!(get.import_name.as_deref(), Some("requests"));
+    assert_eq!((get.line, get.span.start_column, get.span.end_column), (3, 11, 28));
+    assert_eq!((json.line, json.span.start_column, json.span.end_column), (3, 11, 35));
+}
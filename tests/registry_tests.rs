@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tree_sitter::Language;
+use tree_sitter_test::error::AnalysisError;
+use tree_sitter_test::models::analysis_result::AnalysisResult;
+use tree_sitter_test::parser::{parse_file, register_language, supported_extensions, supported_languages, LanguageAnalyzer, LanguageRegistry};
+
+struct FakeAnalyzer;
+
+impl LanguageAnalyzer for FakeAnalyzer {
+    fn name(&self) -> &str {
+        "fake"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["fake"]
+    }
+
+    fn language(&self, _extension: &str) -> Language {
+        tree_sitter_python::language()
+    }
+
+    fn analyze(&self, _source: &str, _path: &Path, _root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        Ok(AnalysisResult { documentation: None, imports: vec![], functions: vec![], classes: vec![] })
+    }
+}
+
+// ---------------------------- Registry ----------------------------
+
+#[test]
+fn test_default_registry_languages() {
+    let registry = LanguageRegistry::default();
+
+    assert_eq!(registry.languages(), vec!["python", "typescript", "javascript"]);
+    assert!(registry.extensions().contains(&"py".to_string()));
+    assert!(registry.extensions().contains(&"tsx".to_string()));
+}
+
+#[test]
+fn test_module_extensions_backends() {
+    let registry = LanguageRegistry::default();
+
+    for file in ["a.mts", "a.cts", "types.d.ts"] {
+        assert_eq!(registry.for_path(Path::new(file)).unwrap().name(), "typescript", "{}", file);
+    }
+    for file in ["a.js", "a.mjs", "a.cjs"] {
+        assert_eq!(registry.for_path(Path::new(file)).unwrap().name(), "javascript", "{}", file);
+    }
+}
+
+#[test]
+fn test_registry_lookup_by_path() {
+    let registry = LanguageRegistry::default();
+
+    assert_eq!(registry.for_path(Path::new("a/b.py")).unwrap().name(), "python");
+    assert_eq!(registry.for_path(Path::new("a/b.tsx")).unwrap().name(), "typescript");
+    assert_eq!(registry.for_path(Path::new("a/b.jsx")).unwrap().name(), "javascript");
+    assert!(registry.for_path(Path::new("a/b.rb")).is_none());
+    assert!(registry.for_path(Path::new("Makefile")).is_none());
+}
+
+#[test]
+fn test_later_registration_overrides_extension() {
+    struct OtherPython;
+    impl LanguageAnalyzer for OtherPython {
+        fn name(&self) -> &str { "other-python" }
+        fn extensions(&self) -> &[&str] { &["py"] }
+        fn language(&self, _extension: &str) -> Language { tree_sitter_python::language() }
+        fn analyze(&self, _source: &str, _path: &Path, _root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+            Ok(AnalysisResult { documentation: None, imports: vec![], functions: vec![], classes: vec![] })
+        }
+    }
+
+    let mut registry = LanguageRegistry::default();
+    registry.register(Arc::new(OtherPython));
+
+    assert_eq!(registry.for_path(Path::new("x.py")).unwrap().name(), "other-python");
+}
+
+#[test]
+fn test_register_external_language() {
+    register_language(Arc::new(FakeAnalyzer));
+
+    assert!(supported_languages().contains(&"fake".to_string()));
+    assert!(supported_extensions().contains(&"fake".to_string()));
+
+    let result = parse_file("whatever", &PathBuf::from("file.fake"), &[]).unwrap();
+    assert!(result.functions.is_empty());
+}
@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use tree_sitter::{Language, Node, Tree};
+use tree_sitter_test::error::AnalysisError;
+use tree_sitter_test::models::analysis_result::AnalysisResult;
+use tree_sitter_test::parser::python::{self, PythonAnalyzer};
+use tree_sitter_test::parser::{parse_file, LanguageAnalyzer, LanguageRegistry};
+use tree_sitter_test::{Analyzer, TextEdit};
+
+const PY_SOURCE: &str = "\
+def foo():
+    return 1
+
+def bar():
+    return 2
+
+class Shape:
+    def area(self):
+        return 0
+";
+
+fn offset(source: &str, needle: &str) -> usize {
+    source.find(needle).unwrap_or_else(|| panic!("{:?} not found", needle))
+}
+
+fn assert_matches_full_parse(source: &str, path: &Path, result: &tree_sitter_test::models::analysis_result::AnalysisResult) {
+    let fresh = parse_file(source, path, &[]).unwrap();
+    assert_eq!(serde_json::to_string(&fresh).unwrap(), serde_json::to_string(result).unwrap());
+}
+
+/// Backend de Python que anota la fila de cada nodo de nivel superior que
+/// se vuelve a extraer.
+struct TrackingPython {
+    extracted: Arc<Mutex<Vec<usize>>>,
+}
+
+impl LanguageAnalyzer for TrackingPython {
+    fn name(&self) -> &str { "tracking-python" }
+    fn extensions(&self) -> &[&str] { &["py"] }
+    fn language(&self, extension: &str) -> Language { PythonAnalyzer.language(extension) }
+    fn analyze(&self, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+        python::parse(source, path, root_path)
+    }
+    fn analyze_tree_filtered(&self, tree: &Tree, source: &str, path: &Path, root_path: &[PathBuf], extract: &dyn Fn(&Node) -> bool) -> Result<AnalysisResult, AnalysisError> {
+        python::analyze_tree_filtered(tree, source, path, root_path, &|node| {
+            let extracted = extract(node);
+            if extracted {
+                self.extracted.lock().unwrap().push(node.start_position().row);
+            }
+            extracted
+        })
+    }
+}
+
+fn tracking_analyzer() -> (Analyzer, Arc<Mutex<Vec<usize>>>) {
+    let extracted = Arc::new(Mutex::new(vec![]));
+    let mut registry = LanguageRegistry::default();
+    registry.register(Arc::new(TrackingPython { extracted: extracted.clone() }));
+    (Analyzer::with_registry(vec![], registry), extracted)
+}
+
+// ---------------------------- Edits ----------------------------
+
+#[test]
+fn test_edit_inside_function_marks_it_modified() {
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, PY_SOURCE).unwrap();
+
+    let at = offset(PY_SOURCE, "return 1") + "return ".len();
+    let changes = session.apply_edit(&TextEdit::new(at, at + 1, "42")).unwrap();
+
+    assert_eq!(changes.modified, vec!["foo"]);
+    assert!(changes.added.is_empty());
+    assert!(changes.removed.is_empty());
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_added_and_removed_functions() {
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, PY_SOURCE).unwrap();
+
+    let start = offset(PY_SOURCE, "def bar");
+    let end = offset(PY_SOURCE, "class Shape");
+    let changes = session.apply_edit(&TextEdit::new(start, end, "def baz(x):\n    return x\n\n")).unwrap();
+
+    assert_eq!(changes.added, vec!["baz"]);
+    assert_eq!(changes.removed, vec!["bar"]);
+    assert!(!changes.modified.contains(&"foo".to_string()));
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_method_changes_are_reported_with_class_prefix() {
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, PY_SOURCE).unwrap();
+
+    let at = offset(PY_SOURCE, "return 0") + "return ".len();
+    let changes = session.apply_edit(&TextEdit::new(at, at + 1, "self.w * self.h")).unwrap();
+
+    assert!(changes.modified.contains(&"Shape.area".to_string()));
+    assert!(!changes.modified.contains(&"foo".to_string()));
+    assert!(!changes.modified.contains(&"bar".to_string()));
+}
+
+#[test]
+fn test_inserting_lines_above_does_not_modify_symbols_below() {
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, PY_SOURCE).unwrap();
+
+    let changes = session.apply_edit(&TextEdit::insert(0, "X = 1\n\n")).unwrap();
+
+    assert!(changes.is_empty(), "{:?}", changes);
+    assert_eq!(session.result().functions[0].line, 3);
+}
+
+#[test]
+fn test_new_import_modifies_functions_that_resolve_through_it() {
+    let source = "def compute():\n    return add(1, 2)\n\ndef other():\n    return 0\n";
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, source).unwrap();
+
+    let changes = session.apply_edit(&TextEdit::insert(0, "from math_utils import add\n")).unwrap();
+
+    assert_eq!(changes.modified, vec!["compute"]);
+    let calls = session.result().functions[0].function_calls.as_ref().unwrap();
+    assert_eq!(calls[0].import_name.as_deref(), Some("math_utils"));
+}
+
+#[test]
+fn test_multiple_edits_are_applied_in_order() {
+    let source = "function a(): void {}\n\nfunction b(): void {}\n";
+    let path = Path::new("session.ts");
+    let mut session = Analyzer::new(vec![]).session(path, source).unwrap();
+
+    let first = TextEdit::insert(source.len(), "function c(): void {}\n");
+    let second = TextEdit::new(offset(source, "a()") , offset(source, "a()") + 1, "renamed");
+    let changes = session.apply_edits(&[first, second]).unwrap();
+
+    assert_eq!(changes.added, vec!["c", "renamed"]);
+    assert_eq!(changes.removed, vec!["a"]);
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_editing_a_decorator_modifies_the_decorated_symbol() {
+    let source = "\
+@cache(maxsize=1)
+def foo():
+    return 1
+
+def bar():
+    return 2
+";
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, source).unwrap();
+
+    let at = offset(source, "maxsize=1") + "maxsize=".len();
+    let changes = session.apply_edit(&TextEdit::new(at, at + 1, "8")).unwrap();
+
+    assert_eq!(changes.modified, vec!["foo"]);
+    assert_eq!(session.result().functions[0].decorators[0].arguments, vec!["maxsize=8"]);
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_nested_functions_are_reported_by_qualified_name() {
+    let source = "\
+def outer():
+    def inner():
+        return 1
+    return inner()
+";
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, source).unwrap();
+
+    let at = offset(source, "return 1") + "return ".len();
+    let changes = session.apply_edit(&TextEdit::new(at, at + 1, "2")).unwrap();
+
+    assert_eq!(changes.modified, vec!["outer", "outer.<locals>.inner"]);
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_only_edited_definitions_are_extracted_again() {
+    let path = Path::new("session.py");
+    let (analyzer, extracted) = tracking_analyzer();
+    let mut session = analyzer.session(path, PY_SOURCE).unwrap();
+
+    let at = offset(PY_SOURCE, "return 2") + "return ".len();
+    let changes = session.apply_edit(&TextEdit::new(at, at + 1, "20\n    return 3")).unwrap();
+
+    assert_eq!(*extracted.lock().unwrap(), vec![3]);
+    assert_eq!(changes.modified, vec!["bar"]);
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_backends_without_filtering_extract_everything() {
+    struct FullPython;
+    impl LanguageAnalyzer for FullPython {
+        fn name(&self) -> &str { "full-python" }
+        fn extensions(&self) -> &[&str] { &["py"] }
+        fn language(&self, extension: &str) -> Language { PythonAnalyzer.language(extension) }
+        fn analyze(&self, source: &str, path: &Path, root_path: &[PathBuf]) -> Result<AnalysisResult, AnalysisError> {
+            python::parse(source, path, root_path)
+        }
+    }
+
+    let path = Path::new("session.py");
+    let mut registry = LanguageRegistry::default();
+    registry.register(Arc::new(FullPython));
+    let mut session = Analyzer::with_registry(vec![], registry).session(path, PY_SOURCE).unwrap();
+
+    let at = offset(PY_SOURCE, "return 1") + "return ".len();
+    let changes = session.apply_edit(&TextEdit::new(at, at + 1, "42")).unwrap();
+
+    assert_eq!(changes.modified, vec!["foo"]);
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_untouched_symbols_are_shifted() {
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, PY_SOURCE).unwrap();
+
+    let at = offset(PY_SOURCE, "return 1");
+    let changes = session.apply_edits(&[
+        TextEdit::insert(at, "x = 1\n    y = 2\n    "),
+        TextEdit::insert(0, "import os\n"),
+    ]).unwrap();
+
+    assert_eq!(changes.modified, vec!["foo"]);
+    let shape = &session.result().classes[0];
+    assert_eq!((shape.line, shape.methods[0].line), (10, 11));
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+#[test]
+fn test_edits_across_definitions_match_a_full_parse() {
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, PY_SOURCE).unwrap();
+
+    let steps: [fn(&str) -> TextEdit; 4] = [
+        // Une `foo` con `bar`
+        |s| TextEdit::new(offset(s, "    return 1"), offset(s, "def bar"), "    return 1\n"),
+        // Saca `area` de la clase
+        |s| TextEdit::delete(offset(s, "    def area"), offset(s, "    def area") + 4),
+        |s| TextEdit::insert(s.len(), "@decorated\nclass Circle:\n    r = 1\n"),
+        |s| TextEdit::delete(0, offset(s, "class Shape")),
+    ];
+    for step in steps {
+        let edit = step(session.source());
+        session.apply_edit(&edit).unwrap();
+        assert_matches_full_parse(session.source(), path, session.result());
+    }
+}
+
+#[test]
+fn test_editing_a_jsdoc_comment_modifies_the_function() {
+    let source = "/** Suma. */\nfunction add(a: number, b: number) { return a + b; }\n\nfunction other() {}\n";
+    let path = Path::new("session.ts");
+    let mut session = Analyzer::new(vec![]).session(path, source).unwrap();
+
+    let at = offset(source, "Suma");
+    let changes = session.apply_edit(&TextEdit::new(at, at + 4, "Suma dos números")).unwrap();
+
+    assert_eq!(changes.modified, vec!["add"]);
+    assert_matches_full_parse(session.source(), path, session.result());
+}
+
+// ---------------------------- Errors ----------------------------
+
+#[test]
+fn test_out_of_range_edit_is_rejected() {
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, PY_SOURCE).unwrap();
+
+    let result = session.apply_edit(&TextEdit::new(5, PY_SOURCE.len() + 10, ""));
+
+    assert!(matches!(result, Err(AnalysisError::InvalidEdit(_))));
+    assert_eq!(session.source(), PY_SOURCE);
+}
+
+#[test]
+fn test_edit_splitting_a_character_is_rejected() {
+    let source = "def saludo():\n    return 'ñ'\n";
+    let path = Path::new("session.py");
+    let mut session = Analyzer::new(vec![]).session(path, source).unwrap();
+
+    let inside = offset(source, "ñ") + 1;
+    let result = session.apply_edit(&TextEdit::insert(inside, "x"));
+
+    assert!(matches!(result, Err(AnalysisError::InvalidEdit(_))));
+}
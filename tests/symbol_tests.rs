@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+use tree_sitter_test::models::analysis_result::AnalysisResult;
+use tree_sitter_test::parser::parse_file;
+use tree_sitter_test::parser::symbols::module_name;
+use tree_sitter_test::{Analyzer, TextEdit};
+
+mod common;
+use common::canonical_temp_project;
+
+fn analyze(root: &Path, file: &str) -> AnalysisResult {
+    Analyzer::new(vec![root.to_path_buf()]).analyze_file(&root.join(file)).unwrap()
+}
+
+// ---------------------------- Module names ----------------------------
+
+#[test]
+fn test_module_name_relative_to_roots() {
+    let roots = vec![PathBuf::from("/repo"), PathBuf::from("/repo/src")];
+
+    assert_eq!(module_name(Path::new("/repo/src/pkg/utils.py"), &roots), "pkg.utils");
+    assert_eq!(module_name(Path::new("/repo/src/pkg/__init__.py"), &roots), "pkg");
+    assert_eq!(module_name(Path::new("/repo/scripts/run.py"), &roots), "scripts.run");
+    assert_eq!(module_name(Path::new("/repo/src/types/api.d.ts"), &roots), "types.api");
+    assert_eq!(module_name(Path::new("/elsewhere/tool.ts"), &roots), "tool");
+}
+
+// ---------------------------- Full names ----------------------------
+
+#[test]
+fn test_full_names_include_module_and_nesting() {
+    let root = canonical_temp_project("symbols_full_names", &[
+        ("app/models.py", "\
+class User:
+    def save(self):
+        def validate():
+            pass
+
+def load():
+    pass"),
+    ]);
+
+    let result = analyze(&root, "app/models.py");
+    let user = &result.classes[0];
+    assert_eq!(user.full_name, "app.models.User");
+    assert_eq!(user.methods[0].full_name, "app.models.User.save");
+    assert_eq!(user.methods[0].functions[0].full_name, "app.models.User.save.<locals>.validate");
+    assert_eq!(result.functions[0].full_name, "app.models.load");
+}
+
+#[test]
+fn test_same_function_in_two_modules_has_different_ids() {
+    let root = canonical_temp_project("symbols_two_utils", &[
+        ("a/utils.py", "def load():\n    pass"),
+        ("b/utils.py", "def load():\n    pass"),
+    ]);
+
+    let a = &analyze(&root, "a/utils.py").functions[0];
+    let b = &analyze(&root, "b/utils.py").functions[0];
+    assert_eq!(a.full_name, "a.utils.load");
+    assert_eq!(b.full_name, "b.utils.load");
+    assert_ne!(a.id, b.id);
+}
+
+#[test]
+fn test_typescript_full_names() {
+    let root = canonical_temp_project("symbols_ts", &[
+        ("src/services/user.ts", "export class UserService {\n    find() {}\n}\nexport const create = () => {};"),
+    ]);
+
+    let result = analyze(&root, "src/services/user.ts");
+    assert_eq!(result.classes[0].full_name, "src.services.user.UserService");
+    assert_eq!(result.classes[0].methods[0].full_name, "src.services.user.UserService.find");
+    assert_eq!(result.functions[0].full_name, "src.services.user.create");
+}
+
+// ---------------------------- Stable IDs ----------------------------
+
+#[test]
+fn test_ids_survive_unrelated_edits() {
+    let source = "def foo():\n    return 1\n\nclass Shape:\n    def area(self):\n        return 0\n";
+    let path = Path::new("shapes.py");
+    let before = parse_file(source, path, &[]).unwrap();
+
+    let mut session = Analyzer::new(vec![]).session(path, source).unwrap();
+    session.apply_edit(&TextEdit::insert(0, "import os\n\n\ndef bar():\n    pass\n\n")).unwrap();
+    let after = session.result();
+
+    assert_eq!(after.functions.len(), 2);
+    assert_eq!(after.functions[1].id, before.functions[0].id);
+    assert_eq!(after.classes[0].id, before.classes[0].id);
+    assert_eq!(after.classes[0].methods[0].id, before.classes[0].methods[0].id);
+    assert_ne!(after.functions[0].id, after.functions[1].id);
+}
+
+#[test]
+fn test_redefinitions_get_distinct_ids() {
+    let source = "\
+class Temperature:
+    @property
+    def celsius(self):
+        return self._c
+
+    @celsius.setter
+    def celsius(self, value):
+        self._c = value";
+    let result = parse_file(source, Path::new("temperature.py"), &[]).unwrap();
+
+    let methods = &result.classes[0].methods;
+    assert_eq!(methods[0].full_name, methods[1].full_name);
+    assert_ne!(methods[0].id, methods[1].id);
+}
+
+#[test]
+fn test_typescript_nested_ids_survive_removing_a_sibling() {
+    let other = "function other() {\n    function helper() {}\n}\n";
+    let outer = "function outer() {\n    function helper() {}\n    class Local {}\n}\n";
+    let path = Path::new("x.ts");
+
+    let both = parse_file(&format!("{}{}", outer, other), path, &[]).unwrap();
+    let alone = parse_file(other, path, &[]).unwrap();
+
+    let local = &both.functions[0].classes[0];
+    assert_eq!(local.full_name, "x.outer.<locals>.Local");
+    assert_eq!(local.parent.as_deref(), Some("outer"));
+    assert_eq!(both.functions[1].functions[0].full_name, "x.other.<locals>.helper");
+    assert_eq!(both.functions[1].functions[0].id, alone.functions[0].functions[0].id);
+}
@@ -1,6 +1,13 @@
 use std::path::PathBuf;
+use tree_sitter_test::models::class_info::FieldKind;
+use tree_sitter_test::models::documentation::DocStyle;
+use tree_sitter_test::models::import_info::{ImportKind, ImportStyle, ImportedName};
+use tree_sitter_test::models::parameter_info::ParameterKind;
 use tree_sitter_test::parser::parse_file;
 
+mod common;
+use common::names;
+
 fn dummy_path() -> PathBuf {
     PathBuf::from("test_file.ts")
 }
@@ -14,31 +21,32 @@ fn dummy_roots() -> Vec<PathBuf> {
 #[test]
 fn test_named_import() {
     let source = "import { add } from './math_utils';";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 1);
     assert_eq!(result.imports[0].name, "math_utils");
-    assert_eq!(result.imports[0].imported_names, vec!["add"]);
+    assert_eq!(names(&result.imports[0]), vec!["add"]);
 }
 
 #[test]
 fn test_named_import_multiple() {
     let source = "import { add, subtract } from './math_utils';";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 1);
     assert_eq!(result.imports[0].name, "math_utils");
-    assert_eq!(result.imports[0].imported_names, vec!["add", "subtract"]);
+    assert_eq!(names(&result.imports[0]), vec!["add", "subtract"]);
 }
 
 #[test]
 fn test_namespace_import() {
     let source = "import * as math from './math_utils';";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 1);
-    assert_eq!(result.imports[0].name, "math");
-    assert!(result.imports[0].imported_names.is_empty());
+    assert_eq!(result.imports[0].name, "math_utils");
+    assert_eq!(result.imports[0].imported_names, vec![ImportedName::namespace("math")]);
+    assert_eq!(result.imports[0].imported("math").map(|n| n.kind), Some(ImportKind::Namespace));
 }
 
 #[test]
@@ -46,12 +54,153 @@ fn test_multiple_imports() {
     let source = "\
 import * as math from './math_utils';
 import { add, subtract } from './math_utils';";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.imports.len(), 2);
-    assert_eq!(result.imports[0].name, "math");
+    assert_eq!(result.imports[0].name, "math_utils");
+    assert_eq!(result.imports[0].imported_names[0].alias.as_deref(), Some("math"));
     assert_eq!(result.imports[1].name, "math_utils");
-    assert_eq!(result.imports[1].imported_names, vec!["add", "subtract"]);
+    assert_eq!(names(&result.imports[1]), vec!["add", "subtract"]);
+}
+
+#[test]
+fn test_default_import() {
+    let source = "import React from 'react';";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 1);
+    assert_eq!(result.imports[0].name, "react");
+    assert_eq!(result.imports[0].imported_names, vec![ImportedName::default_export("React")]);
+    assert_eq!(result.imports[0].imported("React").map(|n| n.name.as_str()), Some("default"));
+}
+
+#[test]
+fn test_aliased_named_import() {
+    let source = "import { add as plus, subtract } from './math_utils';";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(names(&result.imports[0]), vec!["add", "subtract"]);
+    let imported = &result.imports[0].imported_names;
+    assert_eq!(imported[0].alias.as_deref(), Some("plus"));
+    assert!(imported.iter().all(|n| n.kind == ImportKind::Named));
+    assert!(result.imports[0].imported("add").is_none());
+}
+
+#[test]
+fn test_combined_default_and_named_import() {
+    let source = "import React, { useState as state, useEffect } from 'react';";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 1);
+    let bindings: Vec<(&str, &str, ImportKind)> = result.imports[0].imported_names.iter()
+        .map(|n| (n.local_name(), n.name.as_str(), n.kind))
+        .collect();
+    assert_eq!(bindings, vec![
+        ("React", "default", ImportKind::Default),
+        ("state", "useState", ImportKind::Named),
+        ("useEffect", "useEffect", ImportKind::Named),
+    ]);
+}
+
+#[test]
+fn test_combined_default_and_namespace_import() {
+    let source = "import lodash, * as _ from 'lodash';";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports[0].imported_names, vec![
+        ImportedName::default_export("lodash"),
+        ImportedName::namespace("_"),
+    ]);
+}
+
+#[test]
+fn test_side_effect_import() {
+    let source = "import './polyfills';";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 1);
+    assert_eq!(result.imports[0].name, "./polyfills");
+    assert!(result.imports[0].imported_names.is_empty());
+}
+
+// ---------------------------- CommonJS / dynamic imports ----------------------------
+
+fn js_path() -> PathBuf {
+    PathBuf::from("test_file.js")
+}
+
+#[test]
+fn test_require_binding() {
+    let source = "const fs = require('fs');\nconst { join, resolve: res } = require('./paths');";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 2);
+    assert_eq!(result.imports[0].name, "fs");
+    assert_eq!(result.imports[0].style, ImportStyle::Require);
+    assert_eq!(result.imports[0].imported_names, vec![ImportedName::namespace("fs")]);
+
+    assert_eq!(result.imports[1].name, "paths");
+    assert_eq!(result.imports[1].line, 2);
+    assert_eq!(names(&result.imports[1]), vec!["join", "resolve"]);
+    assert_eq!(result.imports[1].imported_names[1].alias.as_deref(), Some("res"));
+}
+
+#[test]
+fn test_require_without_binding() {
+    let source = "require('./polyfills');";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 1);
+    assert_eq!(result.imports[0].name, "./polyfills");
+    assert_eq!(result.imports[0].style, ImportStyle::Require);
+    assert!(result.imports[0].imported_names.is_empty());
+}
+
+#[test]
+fn test_dynamic_import() {
+    let source = "\
+async function load() {
+    const lazy = await import('./lazy');
+    const { default: Widget, render } = await import('./widget');
+    import('./analytics').then(track);
+}";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports.len(), 3);
+    assert!(result.imports.iter().all(|i| i.style == ImportStyle::Dynamic));
+    assert_eq!(result.imports[0].imported_names, vec![ImportedName::namespace("lazy")]);
+    assert_eq!(result.imports[1].imported("Widget").map(|n| n.kind), Some(ImportKind::Default));
+    assert_eq!(result.imports[1].imported("render").map(|n| n.kind), Some(ImportKind::Named));
+    assert_eq!(result.imports[2].name, "./analytics");
+}
+
+#[test]
+fn test_static_import_style() {
+    let source = "import { add } from './math_utils';";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.imports[0].style, ImportStyle::Static);
+}
+
+#[test]
+fn test_calls_through_require_and_dynamic_import() {
+    let source = "\
+const utils = require('./utils');
+const { format } = require('./format');
+async function run() {
+    utils.parse('x');
+    format('y');
+    const lazy = await import('./lazy');
+    lazy.start();
+}";
+    let result = parse_file(source, &js_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    let find = |name: &str| calls.iter().find(|c| c.name == name).unwrap();
+    assert_eq!(find("parse").import_name.as_deref(), Some("utils"));
+    assert!(find("parse").object_name.is_none());
+    assert_eq!(find("format").import_name.as_deref(), Some("format"));
+    assert_eq!(find("start").import_name.as_deref(), Some("lazy"));
 }
 
 // ---------------------------- Functions ----------------------------
@@ -59,7 +208,7 @@ import { add, subtract } from './math_utils';";
 #[test]
 fn test_simple_function() {
     let source = "function hola(): void {}";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     assert_eq!(result.functions[0].name, "hola");
@@ -70,7 +219,7 @@ fn test_simple_function() {
 #[test]
 fn test_function_with_typed_params() {
     let source = "function add(a: number, b: number): number { return a + b; }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     let func = &result.functions[0];
@@ -86,7 +235,7 @@ fn test_function_with_typed_params() {
 #[test]
 fn test_function_with_default_param() {
     let source = "function greet(name: string, greeting: string = \"hello\"): string { return greeting; }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let func = &result.functions[0];
     assert_eq!(func.parameters.len(), 2);
@@ -99,12 +248,56 @@ fn test_function_with_default_param() {
 #[test]
 fn test_function_no_return_type() {
     let source = "function compute(x: number, y: number) { return x + y; }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     assert!(result.functions[0].return_type.is_none());
 }
 
+#[test]
+fn test_rest_parameter_kind() {
+    let source = "function log(level: string, message?: string, ...args: unknown[]) {}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let kinds: Vec<ParameterKind> = result.functions[0].parameters.iter().map(|p| p.kind).collect();
+    assert_eq!(kinds, vec![ParameterKind::PositionalOrKeyword, ParameterKind::PositionalOrKeyword, ParameterKind::VarPositional]);
+    assert_eq!(result.functions[0].parameters[2].param_type.as_deref(), Some("unknown[]"));
+}
+
+#[test]
+fn test_nested_functions_and_classes() {
+    let source = "\
+export function outer() {
+    function helper() {
+        log();
+    }
+    const format = (x: number) => String(x);
+    class Local {
+        run() {
+            const step = () => {};
+        }
+    }
+    [1, 2].map(n => format(n));
+    helper();
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(result.functions.len(), 1);
+    let outer = &result.functions[0];
+    let nested: Vec<(&str, Option<&str>)> = outer.functions.iter()
+        .map(|f| (f.qualified_name.as_str(), f.parent.as_deref()))
+        .collect();
+    assert_eq!(nested, vec![("outer.<locals>.helper", Some("outer")), ("outer.<locals>.format", Some("outer"))]);
+
+    let local = &outer.classes[0];
+    assert_eq!(local.qualified_name, "outer.<locals>.Local");
+    assert_eq!(local.methods[0].qualified_name, "outer.<locals>.Local.run");
+    assert_eq!(local.methods[0].functions[0].qualified_name, "outer.<locals>.Local.run.<locals>.step");
+
+    let calls: Vec<&str> = outer.function_calls.as_ref().unwrap().iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(calls, vec!["map", "format", "helper"]);
+}
+
 // ---------------------------- Function Calls ----------------------------
 
 #[test]
@@ -114,7 +307,7 @@ import { add } from './math_utils';
 function compute(x: number, y: number): number {
     return add(x, y);
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
@@ -129,23 +322,24 @@ import * as math from './math_utils';
 function compute(x: number, y: number): number {
     return math.subtract(x, y);
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
     assert_eq!(calls[0].name, "subtract");
-    assert_eq!(calls[0].import_name.as_deref(), Some("math"));
+    assert_eq!(calls[0].import_name.as_deref(), Some("math_utils"));
 }
 
 #[test]
 fn test_unresolved_call_has_no_import() {
     let source = "function compute(): void { console.log(\"hello\"); }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
     assert_eq!(calls[0].name, "log");
-    assert_eq!(calls[0].import_name.as_deref(), Some("console"));
+    assert!(calls[0].import_name.is_none());
+    assert_eq!(calls[0].object_name.as_deref(), Some("console"));
 }
 
 #[test]
@@ -157,7 +351,7 @@ function compute(x: number, y: number): number {
     const b = subtract(x, y);
     return a + b;
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 2);
@@ -165,10 +359,43 @@ function compute(x: number, y: number): number {
     assert!(calls.iter().any(|c| c.name == "subtract" && c.import_name.as_deref() == Some("math_utils")));
 }
 
+#[test]
+fn test_call_through_aliased_named_import() {
+    let source = "\
+import { add as plus } from './math_utils';
+function compute(x: number, y: number): number {
+    return plus(x, y);
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    assert_eq!(calls[0].name, "add");
+    assert_eq!(calls[0].import_name.as_deref(), Some("math_utils"));
+}
+
+#[test]
+fn test_calls_through_default_import() {
+    let source = "\
+import React, { useState } from 'react';
+import render from './render';
+function App() {
+    const [count] = useState(0);
+    render(React.createElement('div'));
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    let find = |name: &str| calls.iter().find(|c| c.name == name).unwrap();
+    assert_eq!(find("useState").import_name.as_deref(), Some("react"));
+    assert_eq!(find("createElement").import_name.as_deref(), Some("react"));
+    assert!(find("createElement").object_name.is_none());
+    assert_eq!(find("render").import_name.as_deref(), Some("render"));
+}
+
 #[test]
 fn test_arrow_function_top_level() {
     let source = "const add = (a: number, b: number): number => a + b;";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     assert_eq!(result.functions[0].name, "add");
@@ -184,7 +411,7 @@ fn test_async_function() {
 async function fetchData(url: string): Promise<string> {
     return url;
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     assert_eq!(result.functions[0].name, "fetchData");
@@ -195,7 +422,7 @@ async function fetchData(url: string): Promise<string> {
 #[test]
 fn test_const_function_expression() {
     let source = "const add = function(a: number, b: number): number { return a + b; }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions.len(), 1);
     assert_eq!(result.functions[0].name, "add");
@@ -213,7 +440,7 @@ fn test_simple_class() {
 class MyClass {
     myMethod(): void {}
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.classes.len(), 1);
     assert_eq!(result.classes[0].name, "MyClass");
@@ -229,7 +456,7 @@ class Calculator {
         return a * b;
     }
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let method = &result.classes[0].methods[0];
     assert_eq!(method.name, "multiply");
@@ -250,7 +477,7 @@ class MyClass {
         return subtract(x, y);
     }
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.classes[0].methods[0].function_calls.as_ref().unwrap();
     assert_eq!(calls.len(), 1);
@@ -264,7 +491,7 @@ fn test_class_not_added_to_functions() {
 class MyClass {
     myMethod(): void {}
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert!(result.functions.is_empty());
     assert_eq!(result.classes.len(), 1);
@@ -279,7 +506,7 @@ class Geometry {
         this.shapeName = shapeName;
     }
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.classes[0].name, "Geometry");
     let constructor = result.classes[0].methods.iter().find(|m| m.name == "constructor");
@@ -294,19 +521,184 @@ fn test_arrow_function_class_field() {
 class Calculator {
     add = (a: number, b: number): number => a + b;
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.classes[0].methods.len(), 1);
     assert_eq!(result.classes[0].methods[0].name, "add");
     assert_eq!(result.classes[0].methods[0].return_type.as_deref(), Some("number"));
 }
 
+// ---------------------------- JSDoc ----------------------------
+
+#[test]
+fn test_jsdoc_on_exported_function() {
+    let source = "\
+/**
+ * Suma dos números.
+ *
+ * Detalle {@link Math}.
+ * @param a - El primero.
+ * @param {number} [b=0] El segundo,
+ *   opcional.
+ * @param ...rest Los demás.
+ * @returns {number} La suma.
+ * @throws {@link RangeError} Si no son finitos.
+ * @throws Si falla.
+ * @deprecated Usar `sum`.
+ * @example
+ * ```ts
+ * add(1, 2);
+ * @decorated
+ * ```
+ * @internal
+ */
+export function add(a: number, b = 0, ...rest: number[]): number { return a + b; }";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let func = &result.functions[0];
+    let doc = func.documentation.as_ref().unwrap();
+    assert_eq!(doc.style, DocStyle::Jsdoc);
+    assert_eq!(doc.summary.as_deref(), Some("Suma dos números."));
+    assert!(doc.text.starts_with("Suma dos números.\n\nDetalle {@link Math}.\n@param a - El primero."));
+
+    let params: Vec<(&str, Option<&str>, &str)> = doc.params.iter()
+        .map(|p| (p.name.as_str(), p.param_type.as_deref(), p.description.as_str()))
+        .collect();
+    assert_eq!(params, vec![
+        ("a", None, "El primero."),
+        ("b", Some("number"), "El segundo, opcional."),
+        ("...rest", None, "Los demás."),
+    ]);
+
+    let returns = doc.returns.as_ref().unwrap();
+    assert_eq!(returns.return_type.as_deref(), Some("number"));
+    assert_eq!(returns.description, "La suma.");
+    let raises: Vec<(&str, &str)> = doc.raises.iter().map(|r| (r.exception.as_str(), r.description.as_str())).collect();
+    assert_eq!(raises, vec![("RangeError", "Si no son finitos."), ("", "Si falla.")]);
+    assert_eq!(doc.deprecated.as_deref(), Some("Usar `sum`."));
+
+    let tags: Vec<(&str, &str)> = doc.tags.iter().map(|t| (t.name.as_str(), t.value.as_str())).collect();
+    assert_eq!(tags, vec![("example", "```ts\nadd(1, 2);\n@decorated\n```"), ("internal", "")]);
+
+    let descriptions: Vec<Option<&str>> = func.parameters.iter().map(|p| p.description.as_deref()).collect();
+    assert_eq!(descriptions, vec![Some("El primero."), Some("El segundo, opcional."), Some("Los demás.")]);
+}
+
+#[test]
+fn test_jsdoc_on_class_members_and_arrow_const() {
+    let source = "\
+/** Un contador. */
+class Counter {
+    /** Total acumulado. */
+    static total: number = 0;
+    // Comentario común
+    count = 0;
+    /**
+     * Suma uno.
+     * @deprecated
+     */
+    @log()
+    increment(): void {}
+    /** @param delta Cuánto sumar. */
+    add = (delta: number) => delta;
+}
+
+/** Duplica. */
+const double = (x: number) => x * 2;
+
+/* No es JSDoc */
+function plain() {}
+
+/*****/
+function separated() {}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let class = &result.classes[0];
+    assert_eq!(class.documentation.as_ref().unwrap().text, "Un contador.");
+
+    let fields: Vec<(&str, FieldKind, Option<&str>)> = class.fields.iter()
+        .map(|f| (f.name.as_str(), f.kind, f.documentation.as_ref().map(|d| d.text.as_str())))
+        .collect();
+    assert_eq!(fields, vec![("total", FieldKind::Class, Some("Total acumulado.")), ("count", FieldKind::Instance, None)]);
+    assert_eq!(class.fields[0].annotation.as_deref(), Some("number"));
+    assert_eq!(class.fields[0].value.as_deref(), Some("0"));
+
+    let increment = class.methods[0].documentation.as_ref().unwrap();
+    assert_eq!(increment.summary.as_deref(), Some("Suma uno."));
+    assert_eq!(increment.deprecated.as_deref(), Some(""));
+    assert_eq!(class.methods[1].parameters[0].description.as_deref(), Some("Cuánto sumar."));
+
+    assert_eq!(result.functions[0].documentation.as_ref().unwrap().text, "Duplica.");
+    assert!(result.functions[1].documentation.is_none());
+    assert!(result.functions[2].documentation.is_none());
+}
+
+// ---------------------------- Async & Generators ----------------------------
+
+#[test]
+fn test_async_and_generator_flags() {
+    let source = "\
+async function load(): Promise<void> {}
+function* ids(): Generator<number> { yield 1; }
+async function* pages() {}
+const handler = async (req: Request) => req;
+const walk = function* () {};
+class Repo {
+    async find() {}
+    *[Symbol.iterator]() {}
+    static async *scan() {}
+    sync() {}
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let flags: Vec<(&str, bool, bool)> = result.functions.iter()
+        .chain(&result.classes[0].methods)
+        .map(|f| (f.name.as_str(), f.is_async, f.is_generator))
+        .collect();
+    assert_eq!(flags, vec![
+        ("load", true, false),
+        ("ids", false, true),
+        ("pages", true, true),
+        ("handler", true, false),
+        ("walk", false, true),
+        ("find", true, false),
+        ("[Symbol.iterator]", false, true),
+        ("scan", true, true),
+        ("sync", false, false),
+    ]);
+}
+
+#[test]
+fn test_awaited_calls() {
+    let source = "\
+import { fetchUser } from './api';
+
+async function main(db) {
+    const user = await fetchUser(1);
+    const pending = db.save(user);
+    await (db.flush());
+    await Promise.all([notify(user)]);
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls: Vec<(&str, bool)> = result.functions[0].function_calls.as_ref().unwrap().iter()
+        .map(|c| (c.name.as_str(), c.awaited))
+        .collect();
+    assert_eq!(calls, vec![
+        ("fetchUser", true),
+        ("save", false),
+        ("flush", true),
+        ("all", true),
+        ("notify", false),
+    ]);
+}
+
 // ---------------------------- Line Numbers ----------------------------
 
 #[test]
 fn test_ts_function_line_number() {
     let source = "function foo(): void {}\n\nfunction bar(): void {}";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.functions[0].line, 1);
     assert_eq!(result.functions[1].line, 3);
@@ -318,7 +710,7 @@ fn test_ts_class_and_method_line_number() {
 class MyClass {
     myMethod(): void {}
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     assert_eq!(result.classes[0].line, 1);
     assert_eq!(result.classes[0].methods[0].line, 2);
@@ -331,8 +723,141 @@ import { add } from './math_utils';
 function compute(x: number, y: number): number {
     return add(x, y);
 }";
-    let result = parse_file(source, &dummy_path(), &dummy_roots());
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
 
     let calls = result.functions[0].function_calls.as_ref().unwrap();
     assert_eq!(calls[0].line, 3);
-}
\ No newline at end of file
+}
+// ---------------------------- Spans ----------------------------
+
+#[test]
+fn test_ts_class_and_method_spans() {
+    let source = "\
+import { add } from './math_utils';
+class Calc {
+    sum(a: number, b: number): number {
+        return add(a, b);
+    }
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let import = &result.imports[0].span;
+    assert_eq!((import.start_line, import.end_line, import.end_byte), (1, 1, 35));
+
+    let class = &result.classes[0].span;
+    assert_eq!((class.start_line, class.end_line, class.end_column), (2, 6, 1));
+
+    let method = &result.classes[0].methods[0];
+    assert_eq!((method.span.start_line, method.span.start_column, method.span.end_line), (3, 4, 5));
+
+    let param = &method.parameters[1].span;
+    assert_eq!(&source[param.start_byte..param.end_byte], "b: number");
+
+    let call = &method.function_calls.as_ref().unwrap()[0].span;
+    assert_eq!(&source[call.start_byte..call.end_byte], "add(a, b)");
+    assert_eq!((call.start_line, call.start_column), (4, 15));
+}
+
+#[test]
+fn test_ts_local_variable_span() {
+    let source = "\
+function build(): void {
+    const item = new Product();
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let span = &result.functions[0].local_variables[0].span;
+    assert_eq!(&source[span.start_byte..span.end_byte], "item = new Product()");
+    assert_eq!((span.start_line, span.start_column), (2, 10));
+}
+
+// ---------------------------- Call Sites ----------------------------
+
+fn call_positions(result: &tree_sitter_test::models::analysis_result::AnalysisResult) -> Vec<(String, usize, usize)> {
+    let mut calls: Vec<_> = result.functions[0].function_calls.as_ref().unwrap().iter()
+        .map(|c| (c.name.clone(), c.line, c.span.start_column))
+        .collect();
+    calls.sort_by_key(|(_, line, column)| (*line, *column));
+    calls
+}
+
+#[test]
+fn test_ts_each_call_reports_its_own_line() {
+    let source = "\
+function run(): void {
+    setup();
+    execute();
+    teardown();
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(call_positions(&result), vec![
+        ("setup".to_string(), 2, 4),
+        ("execute".to_string(), 3, 4),
+        ("teardown".to_string(), 4, 4),
+    ]);
+}
+
+#[test]
+fn test_ts_calls_in_nested_blocks() {
+    let source = "\
+function run(items: string[]): void {
+    if (items.length) {
+        for (const item of items) {
+            try {
+                handle(item);
+            } catch (e) {
+                report(e);
+            }
+        }
+    }
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(call_positions(&result), vec![
+        ("handle".to_string(), 5, 16),
+        ("report".to_string(), 7, 16),
+    ]);
+}
+
+#[test]
+fn test_ts_call_with_multiline_arguments() {
+    let source = "\
+function run(): void {
+    const total = compute(
+        first(),
+        second(),
+    );
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    assert_eq!(call_positions(&result), vec![
+        ("compute".to_string(), 2, 18),
+        ("first".to_string(), 3, 8),
+        ("second".to_string(), 4, 8),
+    ]);
+
+    let compute = result.functions[0].function_calls.as_ref().unwrap().iter().find(|c| c.name == "compute").unwrap();
+    assert_eq!((compute.span.end_line, compute.span.end_column), (5, 5));
+}
+
+#[test]
+fn test_ts_chained_calls() {
+    let source = "\
+function fetchJson(url: string) {
+    return client
+        .get(url)
+        .json();
+}";
+    let result = parse_file(source, &dummy_path(), &dummy_roots()).unwrap();
+
+    let calls = result.functions[0].function_calls.as_ref().unwrap();
+    assert_eq!(calls.len(), 2);
+
+    let get = calls.iter().find(|c| c.name == "get").unwrap();
+    assert_eq!(get.object_name.as_deref(), Some("client"));
+    assert_eq!((get.line, get.span.end_line), (2, 3));
+
+    let json = calls.iter().find(|c| c.name == "json").unwrap();
+    assert_eq!((json.line, json.span.end_line, json.span.end_column), (2, 4, 15));
+}